@@ -1,17 +1,12 @@
-//!
+//! Utilities for the clients of the shuftle servers.
 
-///
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
-}
+/// Verification of the shuffles performed by the server, see
+/// `shuftlib::common::fairness`.
+pub use shuftlib::common::fairness;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Text rendering of cards, tricks and hands, see `shuftlib::common::render`.
+pub use shuftlib::common::render;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+/// The state of a match as seen by the player using the client, see
+/// `shuftlib::common::view`.
+pub use shuftlib::common::view::PlayerView;
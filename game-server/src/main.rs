@@ -1,4 +1,5 @@
 //!
+#![allow(clippy::empty_docs)]
 
 fn main() {
     println!("Hello, world!");
@@ -0,0 +1,341 @@
+use strum::IntoEnumIterator;
+
+use super::{BriscolaCard, BriscolaRules};
+use crate::common::{
+    auction::{Auction, Bid},
+    cards::{Deck, ItalianRank, Suit},
+    dealing::Dealing,
+    game::MatchRules,
+    hands::{Hand, HandContext, PlayerId, Side, Teams, TrickTakingGame},
+    scoring::{HandScore, ScoreBreakdown, Scoring, Target},
+};
+
+/// The ranks that can be bid, from the one making the lowest bid to the one
+/// making the highest: the weaker the card called, the less likely its holder
+/// is a strong partner.
+const RANKS: [ItalianRank; 10] = [
+    ItalianRank::Ace,
+    ItalianRank::Three,
+    ItalianRank::King,
+    ItalianRank::Knight,
+    ItalianRank::Jack,
+    ItalianRank::Seven,
+    ItalianRank::Six,
+    ItalianRank::Five,
+    ItalianRank::Four,
+    ItalianRank::Two,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Contains the rules of briscola chiamata, played by five players with 8
+/// cards each and no trump turned face up.
+///
+/// Every hand opens with an auction, led by the player after the dealer.
+/// Players bid a rank and the points they commit to make, at least 61: a bid
+/// is higher if it's for more points, or for the same points and a weaker
+/// rank. The winner calls a card of the rank bid, whose suit becomes the trump:
+/// whoever holds it is their partner, but that's only known once the card is
+/// played. The winner might also call one of their own cards, and play alone
+/// against everyone else.
+///
+/// The score is kept for every player, each being a team of their own, while
+/// the sides of a hand are told by `HandContext::side_of`. Every hand adds up
+/// to zero: if the winner and their partner make the points bid, the winner
+/// scores 2 points,
+/// the partner 1 and every other player loses 1. The winner scores 4 if they
+/// played alone. If the contract fails, the same points are lost by the
+/// winner and their partner and won by everyone else.
+///
+/// # Examples
+/// ```
+/// use shuftlib::briscola::chiamata::ChiamataRules;
+/// use shuftlib::common::{auction::Bid, cards::{ItalianRank, ShuffleSeed}, game::{Action, Match}};
+///
+/// let mut chiamata = Match::new(ChiamataRules::default(), ShuffleSeed::from(1)).unwrap();
+/// let first = chiamata.next_to_play().unwrap();
+///
+/// let bid = Bid::Contract { rank: ItalianRank::Three, points: 61 };
+/// chiamata.apply(Action::Bid { player: first, bid }).unwrap();
+/// for _ in 0..4 {
+///     let player = chiamata.next_to_play().unwrap();
+///     chiamata.apply(Action::Bid { player, bid: Bid::Pass }).unwrap();
+/// }
+///
+/// assert_eq!(chiamata.auction().unwrap().winner(), Some(first));
+/// // The winner calls one of the four threes.
+/// assert_eq!(chiamata.legal_moves().len(), 4);
+/// ```
+pub struct ChiamataRules {
+    target: Target,
+}
+
+impl Default for ChiamataRules {
+    fn default() -> Self {
+        Self {
+            target: Target::new(10),
+        }
+    }
+}
+
+impl TrickTakingGame for ChiamataRules {
+    type CardType = BriscolaCard;
+
+    fn players(&self) -> usize {
+        Self::PLAYERS
+    }
+
+    fn tricks(&self) -> usize {
+        Self::TRICKS
+    }
+
+    /// Tricks are taken as in briscola, with the trump being the suit of the
+    /// card called, see `BriscolaRules::determine_taker`.
+    fn determine_taker(
+        &self,
+        cards: &[BriscolaCard],
+        first_to_play: PlayerId,
+        context: &HandContext,
+    ) -> PlayerId {
+        BriscolaRules::default().determine_taker(cards, first_to_play, context)
+    }
+}
+
+impl MatchRules for ChiamataRules {
+    type Signal = ();
+
+    fn deck(&self) -> Deck<BriscolaCard> {
+        Deck::from_vec(Deck::italian().iter().map(|&c| c.into()).collect())
+    }
+
+    /// Every player receives 8 cards, and no card is turned face up.
+    fn dealing(&self, dealer: PlayerId) -> Dealing {
+        Dealing::new(dealer, vec![8])
+    }
+
+    fn auction(&self) -> bool {
+        true
+    }
+
+    /// Any bid higher than the highest one so far, in increasing order, and
+    /// then passing.
+    fn legal_bids(&self, auction: &Auction<BriscolaCard>) -> Vec<Bid<ItalianRank>> {
+        let contracts = Self::contracts();
+        let start = auction
+            .highest()
+            .and_then(|(_, highest)| contracts.iter().position(|&bid| bid == highest))
+            .map_or(0, |index| index + 1);
+
+        let mut bids = contracts[start..].to_vec();
+        bids.push(Bid::Pass);
+        bids
+    }
+
+    /// The cards of the rank bid by the winner, in every suit.
+    fn legal_calls(&self, auction: &Auction<BriscolaCard>) -> Vec<BriscolaCard> {
+        match auction.highest() {
+            Some((_, Bid::Contract { rank, .. })) => Suit::iter()
+                .map(|suit| BriscolaCard::new(rank, suit))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Scoring for ChiamataRules {
+    /// The score is kept for every player, see `compute_score`.
+    fn score_hand(&self, hand: &Hand<Self>, teams: &Teams) -> HandScore {
+        Self::compute_score(hand, teams)
+    }
+
+    /// By default a player wins once they reach 10 points and they're ahead
+    /// of everyone else.
+    fn target(&self) -> Target {
+        self.target
+    }
+}
+
+impl ChiamataRules {
+    /// The number of players of a game of briscola chiamata.
+    pub const PLAYERS: usize = 5;
+    /// The number of tricks of a hand of briscola chiamata.
+    pub const TRICKS: usize = 8;
+    /// The lowest amount of points that can be bid.
+    pub const MIN_POINTS: u32 = 61;
+
+    /// Sets the score to reach to win the match.
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Returns every contract that can be bid, from the lowest to the
+    /// highest.
+    fn contracts() -> Vec<Bid<ItalianRank>> {
+        (Self::MIN_POINTS..=BriscolaRules::POINTS)
+            .flat_map(|points| RANKS.map(|rank| Bid::Contract { rank, points }))
+            .collect()
+    }
+
+    /// Computes the points made by every team in a hand of briscola
+    /// chiamata, where every player plays on their own. The declarer and
+    /// their partner, see `HandContext::side_of`, score if the tricks they
+    /// took are worth the points bid, and lose otherwise.
+    pub fn compute_score(hand: &Hand<Self>, teams: &Teams) -> HandScore {
+        let context = hand.context();
+        let (Some(contract), Some(partner)) = (context.contract(), context.partner()) else {
+            return HandScore::default();
+        };
+
+        let declaring = |player| context.side_of(player) == Some(Side::Declaring);
+        let points: u32 = hand
+            .tricks()
+            .iter()
+            .filter(|t| declaring(t.taker()))
+            .flat_map(|t| t.cards().iter().map(|c| c.value()))
+            .sum();
+        let sign = if points >= contract { 1 } else { -1 };
+        let alone = context.declarer() == Some(partner);
+
+        let mut score = vec![ScoreBreakdown::default(); teams.count()];
+        for player in (0..Self::PLAYERS).filter_map(|id| PlayerId::new(id, Self::PLAYERS)) {
+            let points = match (context.declarer() == Some(player), declaring(player)) {
+                (true, _) if alone => 4,
+                (true, _) => 2,
+                (false, true) => 1,
+                (false, false) => -1,
+            };
+            if let Some(team) = teams.team_of(player).and_then(|t| score.get_mut(*t)) {
+                *team = team.with_contract(team.contract() + sign * points);
+            }
+        }
+        HandScore::new(score)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::common::{
+        auction::{Auction, Bid},
+        cards::{ItalianRank, ShuffleSeed},
+        events::Event,
+        game::{Action, Match, MatchRules},
+        hands::{PlayerId, Side, Teams},
+    };
+
+    use super::ChiamataRules;
+
+    #[test]
+    fn bids_only_go_up() {
+        let rules = ChiamataRules::default();
+        let player = |id| PlayerId::new(id, ChiamataRules::PLAYERS).unwrap();
+        let mut auction = Auction::new(player(0));
+
+        let bids = rules.legal_bids(&auction);
+        assert_eq!(bids.len(), 10 * 60 + 1);
+        assert_eq!(
+            bids[0],
+            Bid::Contract {
+                rank: ItalianRank::Ace,
+                points: 61
+            }
+        );
+        assert_eq!(bids.last(), Some(&Bid::Pass));
+
+        let two = Bid::Contract {
+            rank: ItalianRank::Two,
+            points: 61,
+        };
+        auction.bid(player(0), two);
+        let bids = rules.legal_bids(&auction);
+        assert!(!bids.contains(&two));
+        assert_eq!(
+            bids[0],
+            Bid::Contract {
+                rank: ItalianRank::Ace,
+                points: 62
+            }
+        );
+
+        auction.bid(player(1), Bid::Pass);
+        assert_eq!(rules.legal_calls(&auction).len(), 4);
+    }
+
+    proptest! {
+        #[test]
+        fn the_partner_is_revealed_by_the_card_called(seed: u64, choices in prop::collection::vec(any::<prop::sample::Index>(), 200)) {
+            let rules = ChiamataRules::default();
+            let mut chiamata = Match::new(rules.clone(), ShuffleSeed::from(seed)).unwrap();
+
+            for choice in choices {
+                if !chiamata.hands().is_empty() {
+                    break;
+                }
+                let legal_moves = chiamata.legal_moves();
+                chiamata.apply(*choice.get(&legal_moves)).unwrap();
+
+                let Some(auction) = chiamata.auction() else {
+                    continue;
+                };
+                // Every player keeps their own score, whoever they play with.
+                prop_assert_eq!(chiamata.teams(), &Teams::individual(ChiamataRules::PLAYERS));
+                let context = *chiamata.context();
+                let Some(called) = auction.called() else {
+                    // Nobody but the winner has a side before the card is
+                    // played.
+                    let declaring: Vec<_> = (0..ChiamataRules::PLAYERS)
+                        .filter_map(|id| PlayerId::new(id, ChiamataRules::PLAYERS))
+                        .filter(|&p| context.side_of(p) == Some(Side::Declaring))
+                        .collect();
+                    prop_assert_eq!(declaring, auction.winner().into_iter().collect::<Vec<_>>());
+                    continue;
+                };
+                let played = chiamata.events().iter().find_map(|e| match e {
+                    Event::CardPlayed { player, card, .. } if *card == called => Some(*player),
+                    _ => None,
+                });
+                prop_assert_eq!(context.partner(), played);
+                if let Some(player) = played {
+                    prop_assert_eq!(context.side_of(player), Some(Side::Declaring));
+                }
+            }
+
+            if let Some(hand) = chiamata.hands().first() {
+                let declarer = hand.context().declarer().unwrap();
+                let score = &chiamata.score().hands()[0];
+                prop_assert_eq!(score.teams().len(), ChiamataRules::PLAYERS);
+
+                // Whatever the winner of the auction scored, the other side
+                // lost.
+                let scored: Vec<_> = score.teams().iter().map(|p| p.total()).collect();
+                prop_assert_eq!(scored.iter().sum::<i64>(), 0);
+                prop_assert!([2, 4].contains(&scored[*declarer].abs()));
+                let partners = scored.iter().filter(|&&p| p.signum() == scored[*declarer].signum()).count();
+                prop_assert_eq!(partners, if scored[*declarer].abs() == 4 { 1 } else { 2 });
+            }
+        }
+
+        #[test]
+        fn auctions_are_replayed(seed: u64, moves in 1..40usize, choices in prop::collection::vec(any::<prop::sample::Index>(), 40)) {
+            let rules = ChiamataRules::default();
+            let seed = ShuffleSeed::from(seed);
+            let mut chiamata = Match::new(rules.clone(), seed).unwrap().with_undo(moves);
+            for choice in &choices[..moves] {
+                let legal_moves = chiamata.legal_moves();
+                chiamata.apply(*choice.get(&legal_moves)).unwrap();
+            }
+
+            let log = chiamata.events();
+            let replayed = Match::replay(rules, seed, log, log.len()).unwrap();
+            prop_assert_eq!(replayed.auction(), chiamata.auction());
+            prop_assert_eq!(replayed.legal_moves(), chiamata.legal_moves());
+
+            chiamata.rewind(moves);
+            prop_assert_eq!(chiamata.events().len(), 1);
+            let bidding = matches!(chiamata.legal_moves()[0], Action::Bid { .. });
+            prop_assert!(bidding);
+        }
+    }
+}
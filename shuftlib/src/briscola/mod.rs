@@ -0,0 +1,477 @@
+use std::{fmt::Display, ops::Deref, str::FromStr};
+
+use crate::common::{
+    cards::{
+        Card, Deck, IndexedCard, ItalianCard, ItalianRank, ParseCardError, Ranked, Suit, Suited,
+    },
+    dealing::{Dealing, Trump},
+    game::MatchRules,
+    hands::{Hand, HandContext, PlayerId, Teams, TrickTakingGame},
+    render::Glyph,
+    scoring::{HandScore, ScoreBreakdown, Scoring, Target, TieBreak},
+};
+use std::cmp::Ordering;
+
+/// Briscola chiamata, the five players variant where partners are chosen by an
+/// auction.
+pub mod chiamata;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Contains the rules of the briscola game, for 2, 3 or 4 players. In four
+/// players 0 and 2 play against 1 and 3, otherwise everyone plays on their
+/// own. With three players the two of Hearts is removed from the deck, so that
+/// every player gets the same amount of cards.
+///
+/// Every player receives 3 cards, then the next card is turned face up: its
+/// suit is the trump of the hand, and the card itself lies under the stock.
+/// After every trick each player draws a card, starting from the taker, so the
+/// last one to draw picks up the trump.
+///
+/// # Examples
+/// ```
+/// use shuftlib::{briscola::BriscolaRules, common::{cards::ShuffleSeed, game::Match, hands::TrickTakingGame}};
+///
+/// let rules = BriscolaRules::new(3).unwrap();
+/// assert_eq!(rules.tricks(), 13);
+///
+/// let briscola = Match::new(rules, ShuffleSeed::from(1)).unwrap();
+/// assert_eq!(briscola.stock().len(), 39 - 3 * 3 - 1);
+/// assert_eq!(briscola.context().trump(), Some(briscola.face_up()[0].suit()));
+/// ```
+pub struct BriscolaRules {
+    players: usize,
+    target: Target,
+}
+
+impl Default for BriscolaRules {
+    /// The two players game.
+    fn default() -> Self {
+        Self {
+            players: 2,
+            target: Target::new(1).with_tie_break(TieBreak::Draw),
+        }
+    }
+}
+
+impl TrickTakingGame for BriscolaRules {
+    type CardType = BriscolaCard;
+
+    fn players(&self) -> usize {
+        self.players
+    }
+
+    fn tricks(&self) -> usize {
+        self.deck().len() / self.players
+    }
+
+    /// Contains the logic to determine who won the trick in a briscola game:
+    /// the highest trump played takes the trick. If no trump has been played,
+    /// the trick is taken by the highest card with the same `Suit` of the
+    /// first card played. See the implementation of `Ord` and `PartialOrd` for
+    /// `BriscolaCard` for more info. It assumes the slice `cards` is valid
+    /// for the briscola game, so it assumes there are no duplicates.
+    ///
+    /// # Panics
+    ///
+    /// It can only panic in case of a bug in this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::{hands::{HandContext, TrickTakingGame}, cards::{ItalianRank, Suit}};
+    /// use shuftlib::briscola::{BriscolaCard, BriscolaRules};
+    ///
+    /// let cards = [
+    ///   BriscolaCard::new(ItalianRank::Ace, Suit::Hearts),
+    ///   BriscolaCard::new(ItalianRank::Two, Suit::Clubs),
+    /// ];
+    ///
+    /// let rules = BriscolaRules::default();
+    /// let first = rules.player(0).unwrap();
+    /// let taker = rules.determine_taker(&cards, first, &HandContext::default());
+    /// assert_eq!(taker, rules.player(0).unwrap());
+    ///
+    /// let clubs = HandContext::default().with_trump(Suit::Clubs);
+    /// let taker = rules.determine_taker(&cards, first, &clubs);
+    /// assert_eq!(taker, rules.player(1).unwrap());
+    /// ```
+    #[allow(clippy::expect_used)]
+    fn determine_taker(
+        &self,
+        cards: &[BriscolaCard],
+        first_to_play: PlayerId,
+        context: &HandContext,
+    ) -> PlayerId {
+        let leading_suit = cards[*first_to_play].suit();
+        let (taker, _) = cards
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &c)| {
+                (
+                    Some(c.suit()) == context.trump(),
+                    c.suit() == leading_suit,
+                    c,
+                )
+            })
+            .expect("Max by key returned None. This shouldn't have happened, since it's being called on a non empty slice.");
+
+        PlayerId::new(taker, first_to_play.players()).expect("Initialization of a new PlayerId failed. This shouldn't have happened, since the input usize was computed starting from a fixed length slice.")
+    }
+}
+
+impl MatchRules for BriscolaRules {
+    type Signal = ();
+
+    /// An Italian deck, without the two of Hearts when playing in three.
+    fn deck(&self) -> Deck<BriscolaCard> {
+        let removed = ItalianCard::new(ItalianRank::Two, Suit::Hearts);
+        Deck::from_vec(
+            Deck::italian()
+                .iter()
+                .filter(|&&c| self.players != 3 || c != removed)
+                .map(|&c| c.into())
+                .collect(),
+        )
+    }
+
+    /// Every player receives 3 cards, then the trump is turned face up and
+    /// each player draws a card after every trick.
+    fn dealing(&self, dealer: PlayerId) -> Dealing {
+        Dealing::new(dealer, vec![3])
+            .with_face_up(1)
+            .with_trump(Trump::FaceUp)
+            .with_draws(1)
+    }
+
+    /// Players 0 and 2 play against players 1 and 3 in four, everyone plays
+    /// on their own otherwise.
+    fn teams(&self) -> Teams {
+        match self.players {
+            4 => Teams::partnerships(4, 2).unwrap_or_else(|| Teams::individual(4)),
+            players => Teams::individual(players),
+        }
+    }
+}
+
+impl Scoring for BriscolaRules {
+    fn score_hand(&self, hand: &Hand<Self>, teams: &Teams) -> HandScore {
+        Self::compute_score(hand, teams)
+    }
+
+    /// By default a match is made of a single hand, won by the team with the
+    /// most points. If more teams have the same points, it's a draw.
+    fn target(&self) -> Target {
+        self.target
+    }
+}
+
+impl BriscolaRules {
+    /// The points of all the cards of a deck.
+    pub const POINTS: u32 = 120;
+
+    /// Creates the rules for a game of `players` players, or None if briscola
+    /// can't be played by that many.
+    pub fn new(players: usize) -> Option<Self> {
+        (2..=4).contains(&players).then(|| Self {
+            players,
+            ..Self::default()
+        })
+    }
+
+    /// Sets the score to reach to win the match, so that it can last more
+    /// than one hand.
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Computes the points made by every team in a hand of briscola, by
+    /// adding up the value of the cards taken.
+    pub fn compute_score(hand: &Hand<Self>, teams: &Teams) -> HandScore {
+        let mut cards = vec![0; teams.count()];
+
+        for trick in hand.tricks() {
+            if let Some(points) = teams
+                .team_of(trick.taker())
+                .and_then(|team| cards.get_mut(*team))
+            {
+                *points += trick.cards().iter().map(|c| c.value()).sum::<u32>();
+            }
+        }
+
+        HandScore::new(
+            cards
+                .into_iter()
+                .map(|points| ScoreBreakdown::default().with_cards(points))
+                .collect(),
+        )
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+/// Representation of a card used in the briscola game. It's just a new type
+/// over `ItalianCard`.
+pub struct BriscolaCard {
+    card: ItalianCard,
+}
+
+impl PartialOrd for BriscolaCard {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BriscolaCard {
+    #[allow(clippy::expect_used)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let rank_order = [
+            ItalianRank::Two,
+            ItalianRank::Four,
+            ItalianRank::Five,
+            ItalianRank::Six,
+            ItalianRank::Seven,
+            ItalianRank::Jack,
+            ItalianRank::Knight,
+            ItalianRank::King,
+            ItalianRank::Three,
+            ItalianRank::Ace,
+        ];
+
+        let self_rank_index = rank_order.iter().position(|&r| self.card.rank() == r).expect("The rank of self wasn't found inside the Ord implementation for BriscolaCard. This shouldn't have happened, please file a bug report.");
+        let other_rank_index = rank_order.iter().position(|&r| other.card.rank() == r).expect("The rank of other wasn't found inside the Ord implementation for BriscolaCard. This shouldn't have happened, please file a bug report.");
+
+        self_rank_index.cmp(&other_rank_index)
+    }
+}
+
+impl Display for BriscolaCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.card)
+    }
+}
+
+impl FromStr for BriscolaCard {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<ItalianCard>().map(BriscolaCard::from)
+    }
+}
+
+impl Card for BriscolaCard {}
+
+impl IndexedCard for BriscolaCard {
+    const COUNT: u8 = ItalianCard::COUNT;
+
+    fn index(&self) -> u8 {
+        self.card.index()
+    }
+
+    fn from_index(index: u8) -> Option<Self> {
+        ItalianCard::from_index(index).map(BriscolaCard::from)
+    }
+}
+
+impl Suited for BriscolaCard {
+    fn suit(&self) -> Suit {
+        self.card.suit()
+    }
+}
+
+impl Ranked for BriscolaCard {
+    type Rank = ItalianRank;
+
+    fn rank(&self) -> ItalianRank {
+        self.card.rank()
+    }
+}
+
+impl Glyph for BriscolaCard {
+    fn fmt_glyph(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.card.fmt_glyph(f)
+    }
+}
+
+impl From<ItalianCard> for BriscolaCard {
+    fn from(value: ItalianCard) -> Self {
+        BriscolaCard { card: value }
+    }
+}
+
+impl Deref for BriscolaCard {
+    type Target = ItalianCard;
+
+    fn deref(&self) -> &Self::Target {
+        &self.card
+    }
+}
+
+impl BriscolaCard {
+    /// Gets the value of the card by the rules of the briscola game:
+    /// - Ace = 11
+    /// - 3 = 10
+    /// - King = 4
+    /// - Knight = 3
+    /// - Jack = 2
+    /// - the rest = 0
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::{briscola::BriscolaCard, common::cards::{Suit, ItalianRank}};
+    ///
+    /// let ace = BriscolaCard::new(ItalianRank::Ace, Suit::Hearts);
+    /// let king = BriscolaCard::new(ItalianRank::King, Suit::Spades);
+    /// let seven = BriscolaCard::new(ItalianRank::Seven, Suit::Clubs);
+    /// assert_eq!(ace.value(), 11);
+    /// assert_eq!(king.value(), 4);
+    /// assert_eq!(seven.value(), 0);
+    /// ```
+    pub fn value(&self) -> u32 {
+        match self.rank() {
+            ItalianRank::Ace => 11,
+            ItalianRank::Three => 10,
+            ItalianRank::King => 4,
+            ItalianRank::Knight => 3,
+            ItalianRank::Jack => 2,
+            ItalianRank::Two
+            | ItalianRank::Four
+            | ItalianRank::Five
+            | ItalianRank::Six
+            | ItalianRank::Seven => 0,
+        }
+    }
+
+    /// Generates a new `BriscolaCard` starting from an `ItalianRank` and
+    /// a `Suit`.
+    ///
+    /// # Examples.
+    /// ```
+    /// use shuftlib::common::cards::{ItalianCard, ItalianRank, Suit};
+    /// use shuftlib::briscola::BriscolaCard;
+    ///
+    /// let suit = Suit::Spades;
+    /// let rank = ItalianRank::Ace;
+    /// assert_eq!(*BriscolaCard::new(rank, suit), ItalianCard::new(rank,suit));
+    /// ```
+    pub fn new(rank: ItalianRank, suit: Suit) -> Self {
+        let card = ItalianCard::new(rank, suit);
+
+        BriscolaCard { card }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::common::{
+        cards::{ItalianRank, ShuffleSeed, Suit, Suited},
+        game::{Match, MatchRules},
+        hands::{HandContext, TrickTakingGame},
+    };
+    use prop::collection::hash_set;
+    use proptest::prelude::*;
+
+    use super::{BriscolaCard, BriscolaRules};
+
+    fn briscola_card_strategy() -> impl Strategy<Value = BriscolaCard> {
+        (
+            prop_oneof![
+                Just(ItalianRank::Ace),
+                Just(ItalianRank::Two),
+                Just(ItalianRank::Three),
+                Just(ItalianRank::Four),
+                Just(ItalianRank::Five),
+                Just(ItalianRank::Six),
+                Just(ItalianRank::Seven),
+                Just(ItalianRank::Jack),
+                Just(ItalianRank::Knight),
+                Just(ItalianRank::King),
+            ],
+            suit_strategy(),
+        )
+            .prop_map(|(rank, suit)| BriscolaCard::new(rank, suit))
+    }
+
+    fn suit_strategy() -> impl Strategy<Value = Suit> {
+        prop_oneof![
+            Just(Suit::Hearts),
+            Just(Suit::Clubs),
+            Just(Suit::Spades),
+            Just(Suit::Diamonds),
+        ]
+    }
+
+    #[test]
+    fn every_deck_is_worth_120_points() {
+        for players in 2..=4 {
+            let rules = BriscolaRules::new(players).unwrap();
+            let deck = rules.deck();
+            let points: u32 = deck.iter().map(|c| c.value()).sum();
+
+            assert_eq!(points, BriscolaRules::POINTS);
+            assert_eq!(deck.len(), rules.tricks() * players);
+        }
+        assert!(BriscolaRules::new(1).is_none());
+        assert!(BriscolaRules::new(5).is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn higher_cards_are_worth_at_least_as_much(a in briscola_card_strategy(), b in briscola_card_strategy()) {
+            if a > b {
+                prop_assert!(a.value() >= b.value());
+            }
+        }
+
+        #[test]
+        fn determine_taker_works(cards in hash_set(briscola_card_strategy(), 2..=4), first in 0..4usize, trump in suit_strategy()) {
+            let cards: Vec<BriscolaCard> = cards.into_iter().collect();
+            let rules = BriscolaRules::new(cards.len()).unwrap();
+            let first = rules.player(first % cards.len()).unwrap();
+            let context = HandContext::default().with_trump(trump);
+
+            let taken_with = cards[*rules.determine_taker(&cards, first, &context)];
+            let trumps = cards.iter().filter(|c| c.suit() == trump);
+            let leading_suit = cards[*first].suit();
+
+            // The highest trump takes the trick, otherwise the highest card of
+            // the leading suit does.
+            match trumps.max() {
+                Some(&highest) => prop_assert_eq!(taken_with, highest),
+                None => {
+                    let highest = cards.iter().filter(|c| c.suit() == leading_suit).max();
+                    prop_assert_eq!(Some(&taken_with), highest);
+                }
+            }
+        }
+
+        #[test]
+        fn everyone_plays_every_card(players in 2..=4usize, seed: u64) {
+            let rules = BriscolaRules::new(players).unwrap();
+            let mut briscola = Match::new(rules.clone(), ShuffleSeed::from(seed)).unwrap();
+            let trump = briscola.face_up()[0];
+
+            while briscola.hands().is_empty() {
+                let cards: usize = briscola.players().iter().map(|p| p.hand().len()).sum();
+                prop_assert!(cards <= 3 * players);
+
+                let action = briscola.legal_moves()[0];
+                briscola.apply(action).unwrap();
+            }
+
+            let hand = &briscola.hands()[0];
+            prop_assert_eq!(hand.tricks().len(), rules.tricks());
+            prop_assert_eq!(hand.context().trump(), Some(trump.suit()));
+            // The face up card is picked up by the last one to draw.
+            prop_assert!(hand.tricks().iter().any(|t| t.cards().contains(&trump)));
+
+            let points: i64 = briscola.score().totals().iter().sum();
+            prop_assert_eq!(points, i64::from(BriscolaRules::POINTS));
+        }
+    }
+}
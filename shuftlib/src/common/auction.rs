@@ -0,0 +1,293 @@
+use std::fmt::Display;
+
+use super::{
+    cards::{Ranked, Suited},
+    hands::{HandContext, PlayerId},
+};
+
+/// A bid made during an `Auction`, naming a rank of type `R`, such as
+/// `ItalianRank` for games played with an Italian deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bid<R> {
+    /// The player leaves the auction, and can't bid again in this hand.
+    Pass,
+    /// The player commits to make at least `points` points. What the rank
+    /// means is up to the rules of the game, such as the rank of the card
+    /// called in briscola chiamata.
+    Contract {
+        /// The rank bid.
+        rank: R,
+        /// The minimum points the player commits to make.
+        points: u32,
+    },
+}
+
+/// The reasons why a bid or a call can't be made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionError {
+    /// Cards can't be played until the auction is over and the card has been
+    /// called.
+    InProgress,
+    /// There's no auction going on, or no card to call.
+    Closed,
+    /// It's the turn of another player.
+    NotYourTurn {
+        /// The player who has to bid or call.
+        expected: PlayerId,
+    },
+    /// The rules don't allow this bid now.
+    IllegalBid,
+    /// The rules don't allow to call this card.
+    IllegalCall,
+}
+
+impl Display for AuctionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuctionError::InProgress => write!(f, "the auction isn't over yet"),
+            AuctionError::Closed => write!(f, "there is no auction going on"),
+            AuctionError::NotYourTurn { expected } => {
+                write!(f, "it's the turn of player {}", expected)
+            }
+            AuctionError::IllegalBid => write!(f, "the bid isn't allowed"),
+            AuctionError::IllegalCall => write!(f, "the card can't be called"),
+        }
+    }
+}
+
+impl std::error::Error for AuctionError {}
+
+/// The auction opening a hand, where players take turns to bid until
+/// everyone but one has passed. The winner can then call a card: whoever
+/// holds it becomes their partner, but nobody knows who that is until the card
+/// is played.
+///
+/// Which bids and calls are allowed is decided by the rules of the game, see
+/// `MatchRules::legal_bids` and `MatchRules::legal_calls`.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{auction::{Auction, Bid}, cards::{ItalianCard, ItalianRank}, hands::PlayerId};
+///
+/// let player = |id| PlayerId::new(id, 3).unwrap();
+/// let mut auction = Auction::<ItalianCard>::new(player(1));
+/// let ace = Bid::Contract { rank: ItalianRank::Ace, points: 61 };
+/// let two = Bid::Contract { rank: ItalianRank::Two, points: 61 };
+///
+/// auction.bid(player(1), ace);
+/// auction.bid(player(2), Bid::Pass);
+/// auction.bid(player(0), two);
+/// assert_eq!(auction.next_to_bid(), Some(player(1)));
+///
+/// auction.bid(player(1), Bid::Pass);
+/// assert!(auction.is_over());
+/// assert_eq!(auction.winner(), Some(player(0)));
+/// assert_eq!(auction.highest(), Some((player(0), two)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "C: serde::Serialize, C::Rank: serde::Serialize",
+        deserialize = "C: serde::Deserialize<'de>, C::Rank: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Auction<C>
+where
+    C: Ranked,
+{
+    first: PlayerId,
+    bids: Vec<(PlayerId, Bid<C::Rank>)>,
+    called: Option<C>,
+}
+
+impl<C> Auction<C>
+where
+    C: Copy + Ranked + Suited,
+{
+    /// Creates an auction opened by `first`.
+    pub fn new(first: PlayerId) -> Self {
+        Self {
+            first,
+            bids: Vec::new(),
+            called: None,
+        }
+    }
+
+    /// Returns the player who opened the auction.
+    pub fn first(&self) -> PlayerId {
+        self.first
+    }
+
+    /// Returns every bid made so far, in order.
+    pub fn bids(&self) -> &[(PlayerId, Bid<C::Rank>)] {
+        &self.bids
+    }
+
+    /// Returns true if `player` has left the auction.
+    pub fn has_passed(&self, player: PlayerId) -> bool {
+        self.bids.contains(&(player, Bid::Pass))
+    }
+
+    /// Returns the last contract bid, with the player who bid it. Bids only
+    /// ever go up, so it's also the highest one.
+    pub fn highest(&self) -> Option<(PlayerId, Bid<C::Rank>)> {
+        self.bids
+            .iter()
+            .rev()
+            .find(|(_, bid)| *bid != Bid::Pass)
+            .copied()
+    }
+
+    /// Returns true if no more bids can be made: either everyone but the
+    /// highest bidder has passed, or everyone has.
+    pub fn is_over(&self) -> bool {
+        let players = self.first.players();
+        let passed = self.passed();
+        passed == players || (passed + 1 == players && self.highest().is_some())
+    }
+
+    /// Returns the player who won the auction, once it's over. It's None if
+    /// everyone passed.
+    pub fn winner(&self) -> Option<PlayerId> {
+        self.highest()
+            .filter(|_| self.is_over())
+            .map(|(player, _)| player)
+    }
+
+    /// Returns the player who has to bid next, or None if the auction is
+    /// over. Players who passed are skipped.
+    pub fn next_to_bid(&self) -> Option<PlayerId> {
+        if self.is_over() {
+            return None;
+        }
+
+        let mut player = match self.bids.last() {
+            Some(&(last, _)) => {
+                let mut next = last;
+                next.inc();
+                next
+            }
+            None => self.first,
+        };
+        (0..self.first.players()).find_map(|_| {
+            let candidate = player;
+            player.inc();
+            (!self.has_passed(candidate)).then_some(candidate)
+        })
+    }
+
+    /// Returns the card called by the winner, if it has been called.
+    pub fn called(&self) -> Option<C> {
+        self.called
+    }
+
+    /// Returns the context of a hand played after this auction: the winner is
+    /// the declarer, their bid is the contract and the suit of the card
+    /// called, if any, is the trump.
+    pub fn context(&self) -> HandContext {
+        let mut context = HandContext::default();
+        if let Some((declarer, Bid::Contract { points, .. })) = self.highest() {
+            context = context.with_declarer(declarer).with_contract(points);
+        }
+        if let Some(card) = self.called {
+            context = context.with_trump(card.suit());
+        }
+        context
+    }
+
+    /// Records a bid made by `player`. Whether it's allowed is checked by
+    /// `Match`, before recording it.
+    pub fn bid(&mut self, player: PlayerId, bid: Bid<C::Rank>) {
+        self.bids.push((player, bid));
+    }
+
+    /// Records the card called by the winner.
+    pub fn call(&mut self, card: C) {
+        self.called = Some(card);
+    }
+
+    fn passed(&self) -> usize {
+        self.bids
+            .iter()
+            .filter(|(_, bid)| *bid == Bid::Pass)
+            .count()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::common::{
+        cards::{ItalianCard, ItalianRank, Suit},
+        hands::PlayerId,
+    };
+
+    use super::{Auction, Bid};
+
+    fn contract(points: u32) -> Bid<ItalianRank> {
+        Bid::Contract {
+            rank: ItalianRank::Two,
+            points,
+        }
+    }
+
+    #[test]
+    fn everyone_can_pass() {
+        let player = |id| PlayerId::new(id, 5).unwrap();
+        let mut auction = Auction::<ItalianCard>::new(player(3));
+
+        for id in [3, 4, 0, 1, 2] {
+            assert_eq!(auction.next_to_bid(), Some(player(id)));
+            auction.bid(player(id), Bid::Pass);
+        }
+
+        assert!(auction.is_over());
+        assert_eq!(auction.winner(), None);
+        assert_eq!(auction.context().declarer(), None);
+    }
+
+    #[test]
+    fn the_call_decides_the_trump() {
+        let player = |id| PlayerId::new(id, 2).unwrap();
+        let mut auction = Auction::new(player(0));
+        auction.bid(player(0), contract(70));
+        auction.bid(player(1), Bid::Pass);
+        auction.call(ItalianCard::new(ItalianRank::Two, Suit::Clubs));
+
+        let context = auction.context();
+        assert_eq!(context.declarer(), Some(player(0)));
+        assert_eq!(context.contract(), Some(70));
+        assert_eq!(context.trump(), Some(Suit::Clubs));
+    }
+
+    proptest! {
+        #[test]
+        fn players_who_passed_are_skipped(players in 2..=6usize, first: usize, passes in proptest::collection::vec(any::<bool>(), 1..30)) {
+            let mut auction = Auction::<ItalianCard>::new(PlayerId::new(first % players, players).unwrap());
+            let mut points = 61;
+
+            for pass in passes {
+                let Some(player) = auction.next_to_bid() else {
+                    break;
+                };
+                prop_assert!(!auction.has_passed(player));
+                if pass {
+                    auction.bid(player, Bid::Pass);
+                } else {
+                    auction.bid(player, contract(points));
+                    points += 1;
+                }
+            }
+
+            if let Some(winner) = auction.winner() {
+                prop_assert!(!auction.has_passed(winner));
+                prop_assert_eq!(auction.highest().map(|(p, _)| p), Some(winner));
+            }
+        }
+    }
+}
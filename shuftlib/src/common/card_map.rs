@@ -0,0 +1,101 @@
+use std::{
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use super::cards::IndexedCard;
+
+/// A lookup table holding a value for every distinct card of type `T`. Values
+/// are stored in a contiguous array and accessed through the index of the card,
+/// so lookups never need to hash or compare cards.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{card_map::CardMap, cards::{ItalianCard, ItalianRank, Suit}};
+///
+/// let mut seen = CardMap::<ItalianCard, bool>::default();
+/// let card = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+/// seen[card] = true;
+///
+/// assert!(seen[card]);
+/// assert_eq!(seen.values().filter(|&&s| s).count(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CardMap<T, V>
+where
+    T: IndexedCard,
+{
+    values: Vec<V>,
+    card: PhantomData<T>,
+}
+
+impl<T: IndexedCard, V> CardMap<T, V> {
+    /// Creates a table by computing the value of every card.
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: FnMut(T) -> V,
+    {
+        Self {
+            values: T::all().map(f).collect(),
+            card: PhantomData,
+        }
+    }
+
+    /// Returns the value of the card.
+    pub fn get(&self, card: T) -> &V {
+        &self.values[card.index() as usize]
+    }
+
+    /// Returns a mutable reference to the value of the card.
+    pub fn get_mut(&mut self, card: T) -> &mut V {
+        &mut self.values[card.index() as usize]
+    }
+
+    /// Returns the values, ordered by the index of their card.
+    pub fn values(&self) -> std::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Returns every card with its value, ordered by index.
+    pub fn iter(&self) -> impl Iterator<Item = (T, &V)> {
+        T::all().zip(self.values.iter())
+    }
+}
+
+impl<T: IndexedCard, V: Default> Default for CardMap<T, V> {
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<T: IndexedCard, V> Index<T> for CardMap<T, V> {
+    type Output = V;
+
+    fn index(&self, card: T) -> &Self::Output {
+        self.get(card)
+    }
+}
+
+impl<T: IndexedCard, V> IndexMut<T> for CardMap<T, V> {
+    fn index_mut(&mut self, card: T) -> &mut Self::Output {
+        self.get_mut(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::cards::{FrenchWithJoker, IndexedCard};
+
+    use super::CardMap;
+
+    #[test]
+    fn every_card_has_its_own_value() {
+        let map = CardMap::from_fn(|c: FrenchWithJoker| c.to_string());
+
+        assert_eq!(map.values().len(), FrenchWithJoker::COUNT as usize);
+        map.iter().for_each(|(card, value)| {
+            assert_eq!(*value, card.to_string());
+            assert_eq!(map[card], card.to_string());
+        });
+    }
+}
@@ -0,0 +1,457 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Not, Sub, SubAssign},
+};
+
+use super::cards::{IndexedCard, Suit, Suited};
+
+/// A set of distinct cards, stored as a bitset: the card with index `i` is in
+/// the set when the `i`-th bit is 1. Inserting, removing and looking up cards
+/// are all O(1), and so are the set operations, which makes this type a good
+/// fit for the hands of the players and for keeping track of the cards that
+/// have been seen during a game.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{card_set::CardSet, cards::{ItalianCard, ItalianRank, Suit}};
+///
+/// let ace = ItalianCard::new(ItalianRank::Ace, Suit::Spades);
+/// let two = ItalianCard::new(ItalianRank::Two, Suit::Hearts);
+///
+/// let mut hand = CardSet::new();
+/// assert!(hand.insert(ace));
+/// assert!(hand.insert(two));
+/// // Cards are only contained once.
+/// assert!(!hand.insert(ace));
+///
+/// assert_eq!(hand.len(), 2);
+/// assert_eq!((hand & CardSet::of_suit(Suit::Spades)).len(), 1);
+/// ```
+///
+/// Every card needs its own bit, so sets can only be made of cards whose
+/// `IndexedCard::COUNT` is at most 64, which is checked at compile time:
+/// ```compile_fail
+/// use std::fmt::Display;
+/// use shuftlib::common::{card_set::CardSet, cards::{Card, IndexedCard}};
+///
+/// #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// struct Tile(u8);
+///
+/// impl Display for Tile {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+///
+/// impl Card for Tile {}
+///
+/// impl IndexedCard for Tile {
+///     const COUNT: u8 = 65;
+///
+///     fn index(&self) -> u8 {
+///         self.0
+///     }
+///
+///     fn from_index(index: u8) -> Option<Self> {
+///         (index < Self::COUNT).then_some(Tile(index))
+///     }
+/// }
+///
+/// let tiles = CardSet::<Tile>::new();
+/// ```
+pub struct CardSet<T>
+where
+    T: IndexedCard,
+{
+    bits: u64,
+    card: PhantomData<T>,
+}
+
+impl<T: IndexedCard> CardSet<T> {
+    /// Fails to compile when the cards don't fit in the bits of the set.
+    const FITS: () = assert!(
+        T::COUNT as u32 <= u64::BITS,
+        "a CardSet can only hold cards with a COUNT of at most 64"
+    );
+
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        let () = Self::FITS;
+        Self {
+            bits: 0,
+            card: PhantomData,
+        }
+    }
+
+    /// Creates the set containing every card of type `T`.
+    pub fn full() -> Self {
+        Self::from_bits_truncate(u64::MAX)
+    }
+
+    /// Creates a set from its bit representation. Returns None if any of the
+    /// bits doesn't correspond to a card.
+    pub fn from_bits(bits: u64) -> Option<Self> {
+        let set = Self::from_bits_truncate(bits);
+        (set.bits == bits).then_some(set)
+    }
+
+    /// Creates a set from its bit representation, ignoring the bits that
+    /// don't correspond to any card.
+    pub fn from_bits_truncate(bits: u64) -> Self {
+        let () = Self::FITS;
+        let mask = u64::MAX
+            .checked_shr(u64::BITS - u32::from(T::COUNT))
+            .unwrap_or(0);
+
+        Self {
+            bits: bits & mask,
+            card: PhantomData,
+        }
+    }
+
+    /// Returns the bit representation of the set.
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Adds a card to the set. Returns whether the card was not already in
+    /// the set.
+    pub fn insert(&mut self, card: T) -> bool {
+        let bit = Self::bit(card);
+        let inserted = self.bits & bit == 0;
+        self.bits |= bit;
+        inserted
+    }
+
+    /// Removes a card from the set. Returns whether the card was in the set.
+    pub fn remove(&mut self, card: T) -> bool {
+        let bit = Self::bit(card);
+        let removed = self.bits & bit != 0;
+        self.bits &= !bit;
+        removed
+    }
+
+    /// Returns whether the card is in the set.
+    pub fn contains(&self, card: T) -> bool {
+        self.bits & Self::bit(card) != 0
+    }
+
+    /// Returns the number of cards in the set.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns whether every card of `self` is also in `other`.
+    pub fn is_subset(&self, other: Self) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    /// Returns whether `self` and `other` have no cards in common.
+    pub fn is_disjoint(&self, other: Self) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    /// Returns an iterator over the cards in the set, ordered by index.
+    pub fn iter(&self) -> Iter<T> {
+        Iter {
+            bits: self.bits,
+            card: PhantomData,
+        }
+    }
+
+    /// Returns the subset of the cards satisfying the predicate.
+    pub fn filter<F>(&self, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.iter().filter(predicate).collect()
+    }
+
+    fn bit(card: T) -> u64 {
+        let () = Self::FITS;
+        1 << card.index()
+    }
+}
+
+impl<T: IndexedCard + Suited> CardSet<T> {
+    /// Creates the set containing every card of the given suit.
+    pub fn of_suit(suit: Suit) -> Self {
+        Self::full().filter(|c| c.suit() == suit)
+    }
+}
+
+impl<T: IndexedCard> Clone for CardSet<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: IndexedCard> Copy for CardSet<T> {}
+
+impl<T: IndexedCard> Default for CardSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IndexedCard> PartialEq for CardSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<T: IndexedCard> Eq for CardSet<T> {}
+
+impl<T: IndexedCard> Hash for CardSet<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<T: IndexedCard> Debug for CardSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: IndexedCard> BitOr for CardSet<T> {
+    type Output = Self;
+
+    /// The union of the two sets.
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from_bits_truncate(self.bits | rhs.bits)
+    }
+}
+
+impl<T: IndexedCard> BitOrAssign for CardSet<T> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl<T: IndexedCard> BitAnd for CardSet<T> {
+    type Output = Self;
+
+    /// The intersection of the two sets.
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self::from_bits_truncate(self.bits & rhs.bits)
+    }
+}
+
+impl<T: IndexedCard> BitAndAssign for CardSet<T> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl<T: IndexedCard> Sub for CardSet<T> {
+    type Output = Self;
+
+    /// The cards of `self` which are not in `rhs`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_bits_truncate(self.bits & !rhs.bits)
+    }
+}
+
+impl<T: IndexedCard> SubAssign for CardSet<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: IndexedCard> BitXor for CardSet<T> {
+    type Output = Self;
+
+    /// The cards which are in exactly one of the two sets.
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self::from_bits_truncate(self.bits ^ rhs.bits)
+    }
+}
+
+impl<T: IndexedCard> Not for CardSet<T> {
+    type Output = Self;
+
+    /// The cards which are not in the set.
+    fn not(self) -> Self::Output {
+        Self::from_bits_truncate(!self.bits)
+    }
+}
+
+impl<T: IndexedCard> FromIterator<T> for CardSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: IndexedCard> Extend<T> for CardSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|c| {
+            self.insert(c);
+        });
+    }
+}
+
+impl<T: IndexedCard> IntoIterator for CardSet<T> {
+    type Item = T;
+    type IntoIter = Iter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: IndexedCard> IntoIterator for &CardSet<T> {
+    type Item = T;
+    type IntoIter = Iter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: IndexedCard> From<CardSet<T>> for Vec<T> {
+    fn from(value: CardSet<T>) -> Self {
+        value.iter().collect()
+    }
+}
+
+/// Iterator over the cards of a `CardSet`, ordered by index.
+#[derive(Debug, Clone)]
+pub struct Iter<T> {
+    bits: u64,
+    card: PhantomData<T>,
+}
+
+impl<T: IndexedCard> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bits != 0 {
+            let index = self.bits.trailing_zeros();
+            self.bits &= self.bits - 1;
+            // Sets are only built from valid cards, so every bit maps to one.
+            if let Some(card) = u8::try_from(index).ok().and_then(T::from_index) {
+                return Some(card);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T: IndexedCard> DoubleEndedIterator for Iter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.bits != 0 {
+            let index = u64::BITS - 1 - self.bits.leading_zeros();
+            self.bits &= !(1 << index);
+            if let Some(card) = u8::try_from(index).ok().and_then(T::from_index) {
+                return Some(card);
+            }
+        }
+
+        None
+    }
+}
+
+impl<T: IndexedCard> ExactSizeIterator for Iter<T> {}
+
+impl<T: IndexedCard> FusedIterator for Iter<T> {}
+
+#[cfg(feature = "serde")]
+impl<T: IndexedCard> serde::Serialize for CardSet<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: IndexedCard> serde::Deserialize<'de> for CardSet<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = u64::deserialize(deserializer)?;
+        Self::from_bits(bits).ok_or_else(|| {
+            <D::Error as serde::de::Error>::custom(format!("{:#x} is not a valid card set", bits))
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use crate::common::cards::{Deck, FrenchCard, IndexedCard, ItalianCard, Suit};
+
+    use super::CardSet;
+
+    proptest! {
+        #[test]
+        fn behaves_like_a_set(indexes in vec(0..ItalianCard::COUNT, 0..60)) {
+            let cards: Vec<ItalianCard> = indexes.iter().map(|&i| ItalianCard::from_index(i).unwrap()).collect();
+            let set: CardSet<ItalianCard> = cards.iter().copied().collect();
+
+            let mut expected = cards.clone();
+            expected.sort_by_key(|c| c.index());
+            expected.dedup();
+
+            prop_assert_eq!(set.len(), expected.len());
+            prop_assert_eq!(set.iter().collect::<Vec<_>>(), expected.clone());
+            prop_assert_eq!(set.iter().rev().count(), expected.len());
+            cards.iter().for_each(|&c| assert!(set.contains(c)));
+            prop_assert!((!set).is_disjoint(set));
+            prop_assert_eq!(set | !set, CardSet::full());
+        }
+
+        #[test]
+        fn set_algebra(a in any::<u64>(), b in any::<u64>()) {
+            let a = CardSet::<FrenchCard>::from_bits_truncate(a);
+            let b = CardSet::<FrenchCard>::from_bits_truncate(b);
+
+            prop_assert_eq!((a | b).len() + (a & b).len(), a.len() + b.len());
+            prop_assert!((a - b).is_disjoint(b));
+            prop_assert!((a & b).is_subset(a));
+            prop_assert_eq!(a ^ b, (a - b) | (b - a));
+        }
+    }
+
+    #[test]
+    fn full_sets_contain_whole_decks() {
+        assert_eq!(CardSet::<ItalianCard>::full().len(), Deck::italian().len());
+        assert_eq!(CardSet::<FrenchCard>::full().len(), Deck::french().len());
+        assert!(CardSet::<ItalianCard>::from_bits(1 << 40).is_none());
+    }
+
+    #[test]
+    fn suit_masks() {
+        let spades = CardSet::<FrenchCard>::of_suit(Suit::Spades);
+
+        assert_eq!(spades.len(), 13);
+        assert!(spades.iter().all(|c| c.suit() == Suit::Spades));
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let card = ItalianCard::from_index(7).unwrap();
+        let mut set = CardSet::new();
+
+        assert!(set.insert(card));
+        assert!(set.contains(card));
+        assert!(set.remove(card));
+        assert!(!set.remove(card));
+        assert!(set.is_empty());
+    }
+}
@@ -1,16 +1,71 @@
 use std::{
+    cmp::Ordering,
     fmt::{Debug, Display},
+    hash::Hash,
     ops::{Deref, DerefMut},
+    str::FromStr,
 };
 
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use strum::{EnumIter, FromRepr, IntoEnumIterator};
 
 /// A trait representing a card. The actual implementation depends on the game where this is used.
 pub trait Card: Display + Default + Sized + Debug + Copy + Eq + PartialEq {}
 
+/// A card which can be mapped to a dense index in the range 0..COUNT, where
+/// COUNT is the amount of distinct cards of its type. The mapping is a
+/// bijection, so the index can be used to store cards in a `CardSet`, to look
+/// them up in a `CardMap` or to encode them in a single byte. The `Hash`
+/// implementations of the cards in this crate are consistent with it, and so
+/// are the `Ord` implementations of the cards of the common decks. Cards of a
+/// specific game, such as `TressetteCard`, are ordered by their strength
+/// instead.
+pub trait IndexedCard: Card {
+    /// The amount of distinct cards of this type. It can't be greater than 64,
+    /// which `CardSet` checks at compile time.
+    const COUNT: u8;
+
+    /// Returns the index of the card.
+    fn index(&self) -> u8;
+
+    /// Returns the card with the given index, or None if `index` is not lower
+    /// than `COUNT`.
+    fn from_index(index: u8) -> Option<Self>;
+
+    /// Returns every distinct card of this type, ordered by index.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::cards::{FrenchWithJoker, IndexedCard, ItalianCard};
+    ///
+    /// assert_eq!(ItalianCard::all().count(), 40);
+    /// // Every joker a deck can contain is a different card.
+    /// assert_eq!(FrenchWithJoker::all().count(), 64);
+    /// ```
+    fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
+}
+
+/// A card belonging to one of the four `Suit`s.
+pub trait Suited {
+    /// Returns the suit of the card.
+    fn suit(&self) -> Suit;
+}
+
+/// A card with a rank, such as `ItalianRank` for the cards of an Italian deck.
+pub trait Ranked {
+    /// The ranks of the cards of this type.
+    type Rank: Copy + Debug + Eq + Hash;
+
+    /// Returns the rank of the card.
+    fn rank(&self) -> Self::Rank;
+}
+
 /// Representation of a card that goes into an Italian deck.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItalianCard {
     rank: ItalianRank,
     suit: Suit,
@@ -48,9 +103,88 @@ impl Display for ItalianCard {
     }
 }
 
+impl FromStr for ItalianCard {
+    type Err = ParseCardError;
+
+    /// Parses a card in the same notation used by `Display`: the number of the
+    /// rank followed by the letter of the suit.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::cards::{ItalianCard, ItalianRank, Suit};
+    ///
+    /// let card: ItalianCard = "10S".parse().unwrap();
+    /// assert_eq!(card, ItalianCard::new(ItalianRank::King, Suit::Spades));
+    /// assert_eq!(card.to_string(), "10S");
+    /// assert!("11S".parse::<ItalianCard>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rank, suit) = split_card(s)?;
+        Ok(Self::new(rank.parse()?, suit.parse()?))
+    }
+}
+
 impl Card for ItalianCard {}
 
+impl IndexedCard for ItalianCard {
+    const COUNT: u8 = ITALIAN_CARDS as u8;
+
+    /// Cards are indexed in the same order they have in `Deck::italian`.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::cards::{Deck, IndexedCard, ItalianCard};
+    ///
+    /// for (i, card) in Deck::italian().iter().enumerate() {
+    ///     assert_eq!(card.index() as usize, i);
+    ///     assert_eq!(ItalianCard::from_index(card.index()), Some(*card));
+    /// }
+    /// ```
+    fn index(&self) -> u8 {
+        self.suit as u8 * 10 + self.rank as u8 - 1
+    }
+
+    fn from_index(index: u8) -> Option<Self> {
+        let suit = Suit::from_repr(index / 10)?;
+        let rank = ItalianRank::from_repr(index % 10 + 1)?;
+        Some(Self::new(rank, suit))
+    }
+}
+
+impl Suited for ItalianCard {
+    fn suit(&self) -> Suit {
+        self.suit
+    }
+}
+
+impl Ranked for ItalianCard {
+    type Rank = ItalianRank;
+
+    fn rank(&self) -> ItalianRank {
+        self.rank
+    }
+}
+
+impl Hash for ItalianCard {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u8(self.index());
+    }
+}
+
+impl PartialOrd for ItalianCard {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ItalianCard {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of a card that goes into an French deck.
 pub struct FrenchCard {
     rank: FrenchRank,
@@ -89,22 +223,164 @@ impl Display for FrenchCard {
     }
 }
 
+impl FromStr for FrenchCard {
+    type Err = ParseCardError;
+
+    /// Parses a card in the same notation used by `Display`: the number of the
+    /// rank followed by the letter of the suit.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::cards::{FrenchCard, FrenchRank, Suit};
+    ///
+    /// let card: FrenchCard = "12H".parse().unwrap();
+    /// assert_eq!(card, FrenchCard::new(FrenchRank::Queen, Suit::Hearts));
+    /// assert_eq!(card.to_string(), "12H");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rank, suit) = split_card(s)?;
+        Ok(Self::new(rank.parse()?, suit.parse()?))
+    }
+}
+
 impl Card for FrenchCard {}
 
+impl IndexedCard for FrenchCard {
+    const COUNT: u8 = FRENCH_CARDS as u8;
+
+    /// Cards are indexed in the same order they have in `Deck::french`.
+    fn index(&self) -> u8 {
+        self.suit as u8 * 13 + self.rank as u8 - 1
+    }
+
+    fn from_index(index: u8) -> Option<Self> {
+        let suit = Suit::from_repr(index / 13)?;
+        let rank = FrenchRank::from_repr(index % 13 + 1)?;
+        Some(Self::new(rank, suit))
+    }
+}
+
+impl Suited for FrenchCard {
+    fn suit(&self) -> Suit {
+        self.suit
+    }
+}
+
+impl Ranked for FrenchCard {
+    type Rank = FrenchRank;
+
+    fn rank(&self) -> FrenchRank {
+        self.rank
+    }
+}
+
+impl Hash for FrenchCard {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u8(self.index());
+    }
+}
+
+impl PartialOrd for FrenchCard {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrenchCard {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
 /// A Joker card, present in some card games. Its function depends on the game.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub struct Joker;
+/// A deck can contain up to `Joker::COUNT` jokers, each with its own number,
+/// so that they are different cards and can be held in the same `CardSet`.
+/// `Joker::default()` is the first one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
+pub struct Joker(u8);
+
+impl Joker {
+    /// Returns the joker with the given number, starting from 0, or None if
+    /// `number` is not lower than `Joker::COUNT`.
+    pub fn nth(number: u8) -> Option<Self> {
+        Self::from_index(number)
+    }
+
+    /// Getter for the number of the joker.
+    pub fn number(&self) -> u8 {
+        self.0
+    }
+}
 
 impl Card for Joker {}
 
+impl IndexedCard for Joker {
+    /// As many jokers as fit next to the 52 French cards in a `CardSet`.
+    const COUNT: u8 = 12;
+
+    fn index(&self) -> u8 {
+        self.0
+    }
+
+    fn from_index(index: u8) -> Option<Self> {
+        (index < Self::COUNT).then_some(Joker(index))
+    }
+}
+
+impl TryFrom<u8> for Joker {
+    type Error = ParseCardError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Joker::nth(value).ok_or_else(|| ParseCardError::InvalidCard(value.to_string()))
+    }
+}
+
+impl From<Joker> for u8 {
+    fn from(value: Joker) -> Self {
+        value.0
+    }
+}
+
+/// The first joker is written as JK, the following ones as JK2, JK3 and so
+/// on. Every joker is written in a single way, so JK1 and JK02 aren't jokers.
 impl Display for Joker {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "JK")
+        match self.0 {
+            0 => write!(f, "JK"),
+            n => write!(f, "JK{}", n + 1),
+        }
+    }
+}
+
+impl FromStr for Joker {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = match s.strip_prefix("JK") {
+            Some("") => Some(0),
+            Some(number) => number
+                .parse::<u8>()
+                .ok()
+                .filter(|&n| n >= 2 && n.to_string() == number)
+                .map(|n| n - 1),
+            None if s.is_empty() => return Err(ParseCardError::Empty),
+            None => None,
+        };
+
+        number
+            .and_then(Joker::nth)
+            .ok_or_else(|| ParseCardError::InvalidCard(s.to_string()))
     }
 }
 
 /// A variant of the French card, which can either be an actual French card or a joker.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FrenchWithJoker {
     /// A standard French card.
     Normal(FrenchCard),
@@ -113,6 +389,43 @@ pub enum FrenchWithJoker {
 }
 impl Card for FrenchWithJoker {}
 
+impl IndexedCard for FrenchWithJoker {
+    const COUNT: u8 = FrenchCard::COUNT + Joker::COUNT;
+
+    /// French cards keep their own index, jokers come after them.
+    fn index(&self) -> u8 {
+        match self {
+            FrenchWithJoker::Normal(c) => c.index(),
+            FrenchWithJoker::Joker(j) => FrenchCard::COUNT + j.index(),
+        }
+    }
+
+    fn from_index(index: u8) -> Option<Self> {
+        match index.checked_sub(FrenchCard::COUNT) {
+            None => FrenchCard::from_index(index).map(FrenchWithJoker::Normal),
+            Some(index) => Joker::from_index(index).map(FrenchWithJoker::Joker),
+        }
+    }
+}
+
+impl Hash for FrenchWithJoker {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u8(self.index());
+    }
+}
+
+impl PartialOrd for FrenchWithJoker {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrenchWithJoker {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
 impl Default for FrenchWithJoker {
     fn default() -> Self {
         Self::Normal(FrenchCard {
@@ -131,7 +444,19 @@ impl Display for FrenchWithJoker {
     }
 }
 
+impl FromStr for FrenchWithJoker {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<Joker>() {
+            Ok(joker) => Ok(FrenchWithJoker::Joker(joker)),
+            Err(_) => s.parse().map(FrenchWithJoker::Normal),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter, FromRepr, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 /// The rank of the card. In an Italian deck, ranks go from the ace to the 7, then they also have a jack, knight and king,
 /// In most games they each have a different value that depends on the game itself.
@@ -158,7 +483,25 @@ pub enum ItalianRank {
     King,
 }
 
+impl Display for ItalianRank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", *self as u8)
+    }
+}
+
+impl FromStr for ItalianRank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .ok()
+            .and_then(Self::from_repr)
+            .ok_or_else(|| ParseCardError::InvalidRank(s.to_string()))
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter, FromRepr, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 /// The rank of the card. In a French deck, ranks go from the ace to 10, then there is a jack, queen and king,
 /// In most games they each have a different value that depends on the game itself.
@@ -191,7 +534,26 @@ pub enum FrenchRank {
     King,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Hash)]
+impl Display for FrenchRank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", *self as u8)
+    }
+}
+
+impl FromStr for FrenchRank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .ok()
+            .and_then(Self::from_repr)
+            .ok_or_else(|| ParseCardError::InvalidRank(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, FromRepr, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
 /// The 4 suits of a standard deck. They have an equivalent in pretty much all regional decks.
 /// In some games they have a hierarchical order.
 pub enum Suit {
@@ -217,7 +579,167 @@ impl Display for Suit {
     }
 }
 
-#[derive(Default)]
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "H" => Ok(Suit::Hearts),
+            "D" => Ok(Suit::Diamonds),
+            "C" => Ok(Suit::Clubs),
+            "S" => Ok(Suit::Spades),
+            _ => Err(ParseCardError::InvalidSuit(s.to_string())),
+        }
+    }
+}
+
+/// The reasons why parsing a card, a rank or a suit might fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    /// The string is empty.
+    Empty,
+    /// The string isn't the number of any rank.
+    InvalidRank(String),
+    /// The string isn't the letter of any suit.
+    InvalidSuit(String),
+    /// The string doesn't represent any card.
+    InvalidCard(String),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "cannot parse a card from an empty string"),
+            ParseCardError::InvalidRank(s) => write!(f, "\"{}\" is not a valid rank", s),
+            ParseCardError::InvalidSuit(s) => write!(f, "\"{}\" is not a valid suit", s),
+            ParseCardError::InvalidCard(s) => write!(f, "\"{}\" is not a valid card", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// Splits a card into its rank and suit. The suit is always the last
+/// character.
+fn split_card(s: &str) -> Result<(&str, &str), ParseCardError> {
+    match s.char_indices().last() {
+        None => Err(ParseCardError::Empty),
+        Some((0, _)) => Err(ParseCardError::InvalidCard(s.to_string())),
+        Some((i, _)) => Ok(s.split_at(i)),
+    }
+}
+
+/// The error returned when parsing a list of cards fails. It points to the
+/// card that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCardsError {
+    /// The position of the invalid card in the list, starting from 0.
+    pub position: usize,
+    /// The reason why the card couldn't be parsed.
+    pub error: ParseCardError,
+}
+
+impl Display for ParseCardsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "card {}: {}", self.position, self.error)
+    }
+}
+
+impl std::error::Error for ParseCardsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Parses a list of whitespace separated cards, such as the hand of a player
+/// or the cards played in a trick.
+///
+/// # Errors
+///
+/// Fails with the position of the first card that couldn't be parsed.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::cards::{self, ItalianCard, ItalianRank, Suit};
+///
+/// let hand: Vec<ItalianCard> = cards::parse_cards("1H 10S  3C").unwrap();
+/// assert_eq!(hand.len(), 3);
+/// assert_eq!(hand[1], ItalianCard::new(ItalianRank::King, Suit::Spades));
+///
+/// let error = cards::parse_cards::<ItalianCard>("1H 1X").unwrap_err();
+/// assert_eq!(error.position, 1);
+/// ```
+pub fn parse_cards<T>(s: &str) -> Result<Vec<T>, ParseCardsError>
+where
+    T: Card + FromStr<Err = ParseCardError>,
+{
+    s.split_whitespace()
+        .enumerate()
+        .map(|(position, card)| {
+            card.parse()
+                .map_err(|error| ParseCardsError { position, error })
+        })
+        .collect()
+}
+
+/// The seed used to shuffle a `Deck`. Every shuffle performed from the same
+/// seed produces the same permutation, so storing the seed is enough to
+/// reconstruct a dealt hand exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShuffleSeed([u8; 32]);
+
+impl ShuffleSeed {
+    /// Creates a seed from its raw bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Generates a new seed from the entropy of the operating system.
+    pub fn random() -> Self {
+        let mut bytes = [0; 32];
+        rand::rngs::OsRng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Returns the raw bytes of the seed.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns the random number generator deterministically derived from
+    /// this seed.
+    pub fn rng(&self) -> ChaCha20Rng {
+        ChaCha20Rng::from_seed(self.0)
+    }
+}
+
+impl From<[u8; 32]> for ShuffleSeed {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<u64> for ShuffleSeed {
+    fn from(value: u64) -> Self {
+        let mut bytes = [0; 32];
+        ChaCha20Rng::seed_from_u64(value).fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+}
+
+impl Display for ShuffleSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 /// Represents a deck of cards. Cards can be added or removed at will.
 pub struct Deck<T>
 where
@@ -257,7 +779,18 @@ impl Deck<FrenchCard> {
     }
 
     /// Creates a new 52 cards French deck, with the addition of the specified amount of jokers.
+    /// Every joker is a different card, so no more than `Joker::COUNT` of
+    /// them are added, however many are requested.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::cards::{Deck, IndexedCard, Joker};
+    ///
+    /// assert_eq!(Deck::french_with_jokers(2).len(), 54);
+    /// assert_eq!(Deck::french_with_jokers(u8::MAX).len(), 52 + usize::from(Joker::COUNT));
+    /// ```
     pub fn french_with_jokers(jokers: u8) -> Deck<FrenchWithJoker> {
+        let jokers = jokers.min(Joker::COUNT);
         let mut cards = Vec::with_capacity(FRENCH_CARDS + jokers as usize);
         for suit in Suit::iter() {
             for rank in FrenchRank::iter() {
@@ -265,32 +798,86 @@ impl Deck<FrenchCard> {
             }
         }
 
-        for _ in 0..jokers {
-            cards.push(FrenchWithJoker::Joker(Joker {}));
-        }
+        cards.extend(
+            (0..jokers)
+                .filter_map(Joker::nth)
+                .map(FrenchWithJoker::Joker),
+        );
 
         Deck { cards }
     }
 }
 
 impl<T: Card> Deck<T> {
-    /// Performs a random permutation on the deck with the Fisher–Yates shuffle algorithm, repeated 10 times.
+    /// Performs a random permutation on the deck with the Fisher–Yates shuffle algorithm.
     pub fn shuffle(&mut self) {
-        let mut rng = rand::thread_rng();
-        let max = self.cards.len();
-        for _ in 0..10 {
-            for i in 0..max - 2 {
-                let j = rng.gen_range(i..max);
-                self.cards.swap(i, j);
-            }
-        }
+        self.shuffle_with(&mut rand::thread_rng());
+    }
+
+    /// Same as `shuffle`, but the randomness is taken from the given source.
+    /// Shuffling two equal decks with two generators in the same state always
+    /// produces the same permutation.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha20Rng;
+    /// use shuftlib::common::cards::Deck;
+    ///
+    /// let mut deck1 = Deck::italian();
+    /// let mut deck2 = Deck::italian();
+    /// deck1.shuffle_with(&mut ChaCha20Rng::seed_from_u64(42));
+    /// deck2.shuffle_with(&mut ChaCha20Rng::seed_from_u64(42));
+    ///
+    /// assert_eq!(*deck1, *deck2);
+    /// ```
+    pub fn shuffle_with<R>(&mut self, rng: &mut R)
+    where
+        R: RngCore + ?Sized,
+    {
+        self.cards.shuffle(rng);
+    }
+
+    /// Shuffles the deck with a freshly generated `ShuffleSeed` and returns
+    /// it, so that the resulting order can be reconstructed later with
+    /// `shuffle_from_seed`.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::cards::Deck;
+    ///
+    /// let mut deck = Deck::italian();
+    /// let seed = deck.shuffle_seeded();
+    ///
+    /// let mut replayed = Deck::italian();
+    /// replayed.shuffle_from_seed(seed);
+    /// assert_eq!(*deck, *replayed);
+    /// ```
+    pub fn shuffle_seeded(&mut self) -> ShuffleSeed {
+        let seed = ShuffleSeed::random();
+        self.shuffle_from_seed(seed);
+        seed
+    }
+
+    /// Shuffles the deck deterministically, using the given seed.
+    pub fn shuffle_from_seed(&mut self, seed: ShuffleSeed) {
+        self.shuffle_with(&mut seed.rng());
     }
 
-    /// Adds a card in a random position inside the deck.
+    /// Adds a card in a random position inside the deck, top and bottom
+    /// included.
     pub fn shuffle_card(&mut self, card: T) {
-        let mut rng = rand::thread_rng();
+        self.shuffle_card_with(card, &mut rand::thread_rng());
+    }
+
+    /// Same as `shuffle_card`, but the position is taken from the given source
+    /// of randomness.
+    pub fn shuffle_card_with<R>(&mut self, card: T, rng: &mut R)
+    where
+        R: RngCore + ?Sized,
+    {
         let max = self.cards.len();
-        let position = rng.gen_range(1..max);
+        let position = rng.gen_range(0..=max);
         self.cards.insert(position, card);
     }
 
@@ -353,8 +940,20 @@ where
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
-    use crate::common::cards::Deck;
+    use std::collections::HashSet;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use crate::common::{
+        card_set::CardSet,
+        cards::{
+            parse_cards, Deck, FrenchCard, FrenchWithJoker, IndexedCard, ItalianCard, ItalianRank,
+            Joker, ParseCardError, ShuffleSeed, Suit,
+        },
+    };
 
     #[test]
     fn should_shuffle() {
@@ -369,4 +968,178 @@ mod tests {
 
         assert_ne!(count_of_different_cards, 0);
     }
+
+    #[test]
+    fn same_seed_same_permutation() {
+        let seed = ShuffleSeed::from(1234);
+
+        let mut deck1 = Deck::italian();
+        deck1.shuffle_from_seed(seed);
+        let mut deck2 = Deck::italian();
+        deck2.shuffle_from_seed(seed);
+
+        assert_eq!(deck1.cards, deck2.cards);
+    }
+
+    #[test]
+    fn different_seeds_different_permutations() {
+        let mut deck1 = Deck::italian();
+        deck1.shuffle_from_seed(ShuffleSeed::from(1));
+        let mut deck2 = Deck::italian();
+        deck2.shuffle_from_seed(ShuffleSeed::from(2));
+
+        assert_ne!(deck1.cards, deck2.cards);
+    }
+
+    #[test]
+    fn shuffle_card_with_is_reproducible() {
+        let card = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+        let mut deck1 = Deck::italian();
+        deck1.shuffle_card_with(card, &mut ChaCha20Rng::seed_from_u64(7));
+        let mut deck2 = Deck::italian();
+        deck2.shuffle_card_with(card, &mut ChaCha20Rng::seed_from_u64(7));
+
+        assert_eq!(deck1.cards, deck2.cards);
+    }
+
+    #[test]
+    fn small_decks_are_shuffled() {
+        let ace = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+        let two = ItalianCard::new(ItalianRank::Two, Suit::Hearts);
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+        let mut empty: Deck<ItalianCard> = Deck::new();
+        empty.shuffle_with(&mut rng);
+        assert!(empty.is_empty());
+
+        let mut one = Deck::from_vec(vec![ace]);
+        one.shuffle_with(&mut rng);
+        assert_eq!(one.cards, vec![ace]);
+
+        // Both orders of a 2 cards deck come up.
+        let orders: HashSet<Vec<ItalianCard>> = (0..100)
+            .map(|_| {
+                let mut two = Deck::from_vec(vec![ace, two]);
+                two.shuffle_with(&mut rng);
+                two.cards
+            })
+            .collect();
+        assert_eq!(orders.len(), 2);
+    }
+
+    #[test]
+    fn cards_can_be_shuffled_anywhere() {
+        let ace = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+        let two = ItalianCard::new(ItalianRank::Two, Suit::Hearts);
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+        let mut empty = Deck::new();
+        empty.shuffle_card_with(ace, &mut rng);
+        assert_eq!(empty.cards, vec![ace]);
+
+        // The card can end up both at the bottom and at the top of the deck.
+        let positions: HashSet<usize> = (0..100)
+            .map(|_| {
+                let mut one = Deck::from_vec(vec![two]);
+                one.shuffle_card_with(ace, &mut rng);
+                one.iter().position(|c| *c == ace).unwrap()
+            })
+            .collect();
+        assert_eq!(positions, HashSet::from([0, 1]));
+    }
+
+    #[test]
+    fn cards_round_trip() {
+        for card in Deck::italian().iter() {
+            assert_eq!(card.to_string().parse::<ItalianCard>(), Ok(*card));
+        }
+        for card in Deck::french_with_jokers(Joker::COUNT).iter() {
+            assert_eq!(card.to_string().parse::<FrenchWithJoker>(), Ok(*card));
+        }
+
+        let hand = Deck::french()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parse_cards::<FrenchCard>(&hand.join(" ")).unwrap(),
+            Deck::french().to_vec()
+        );
+    }
+
+    #[test]
+    fn invalid_cards() {
+        assert_eq!("".parse::<ItalianCard>(), Err(ParseCardError::Empty));
+        assert_eq!(
+            "H".parse::<ItalianCard>(),
+            Err(ParseCardError::InvalidCard("H".to_string()))
+        );
+        assert_eq!(
+            "0H".parse::<ItalianCard>(),
+            Err(ParseCardError::InvalidRank("0".to_string()))
+        );
+        assert_eq!(
+            "1Z".parse::<ItalianCard>(),
+            Err(ParseCardError::InvalidSuit("Z".to_string()))
+        );
+        assert_eq!(
+            "13é".parse::<FrenchCard>(),
+            Err(ParseCardError::InvalidSuit("é".to_string()))
+        );
+    }
+
+    fn assert_bijection<T: IndexedCard + Ord + std::hash::Hash>() {
+        let cards: Vec<T> = T::all().collect();
+        let distinct: std::collections::HashSet<T> = cards.iter().copied().collect();
+
+        assert_eq!(distinct.len(), cards.len());
+        assert!(cards.windows(2).all(|w| w[0] < w[1]));
+        for (i, card) in cards.iter().enumerate() {
+            assert_eq!(card.index() as usize, i);
+        }
+        assert!(T::from_index(T::COUNT).is_none());
+    }
+
+    #[test]
+    fn indexes_are_dense_and_unique() {
+        assert_bijection::<ItalianCard>();
+        assert_bijection::<FrenchCard>();
+        assert_bijection::<Joker>();
+        assert_bijection::<FrenchWithJoker>();
+
+        assert_eq!(FrenchWithJoker::COUNT, 64);
+        assert_eq!(
+            FrenchWithJoker::all().collect::<Vec<_>>(),
+            Deck::french_with_jokers(Joker::COUNT).to_vec()
+        );
+    }
+
+    #[test]
+    fn jokers_are_different_cards() {
+        let hand: CardSet<FrenchWithJoker> = Deck::french_with_jokers(2).iter().copied().collect();
+        assert_eq!(hand.len(), 54);
+
+        assert_eq!("JK".parse::<Joker>(), Ok(Joker::default()));
+        assert_eq!("JK2".parse::<Joker>(), Ok(Joker::nth(1).unwrap()));
+        assert!("JK0".parse::<Joker>().is_err());
+        assert!("JK13".parse::<Joker>().is_err());
+    }
+
+    #[test]
+    fn jokers_are_written_in_a_single_way() {
+        for text in ["JK1", "JK02", "JK+2"] {
+            assert_eq!(
+                text.parse::<Joker>(),
+                Err(ParseCardError::InvalidCard(text.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn french_decks_have_a_limited_amount_of_jokers() {
+        assert_eq!(
+            Deck::french_with_jokers(Joker::COUNT + 1),
+            Deck::french_with_jokers(Joker::COUNT)
+        );
+    }
 }
@@ -0,0 +1,443 @@
+use std::fmt::Display;
+
+use super::{
+    cards::{Card, Deck, Suit, Suited},
+    hands::{Player, PlayerId, TrickTakingGame},
+};
+
+/// The direction the cards are dealt in. `Clockwise` follows increasing
+/// `PlayerId`s, which is also the order players take their turns in an
+/// `OngoingTrick`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// From a player to the one with the following `PlayerId`.
+    #[default]
+    Clockwise,
+    /// From a player to the one with the previous `PlayerId`.
+    Counterclockwise,
+}
+
+/// How the trump suit of a hand is chosen when its cards are dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trump {
+    /// The trump is always the same suit.
+    Fixed(Suit),
+    /// The trump is the suit of the first card turned face up, as in briscola.
+    FaceUp,
+}
+
+/// Describes how the cards are distributed to the players. The deal is made
+/// of rounds: in each round every player receives a packet of cards, starting
+/// from the player next to the dealer. Once every round is over, the requested
+/// amount of cards is turned face up and the rest of the deck is left as it
+/// is, to be used as stock. Players can draw from the stock after every
+/// trick, see `with_draws`.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{dealing::{Dealing, Direction, Trump}, hands::PlayerId};
+///
+/// // Tressette: two packets of 5 cards each.
+/// let tressette = Dealing::new(PlayerId::new(0, 4).unwrap(), vec![5, 5]);
+/// // Briscola: 3 cards each, then the trump is turned face up and everyone
+/// // draws a card after each trick.
+/// let briscola = Dealing::new(PlayerId::new(0, 2).unwrap(), vec![3])
+///     .with_face_up(1)
+///     .with_trump(Trump::FaceUp)
+///     .with_draws(1);
+/// // Bridge: 13 cards one by one, dealt by the second player.
+/// let bridge = Dealing::new(PlayerId::new(1, 4).unwrap(), vec![1; 13]);
+///
+/// assert_eq!(tressette.cards_per_player(), 10);
+/// assert_eq!(bridge.cards_per_player(), 13);
+/// assert_eq!(briscola.direction(), Direction::Clockwise);
+/// assert_eq!(briscola.draws(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dealing {
+    packets: Vec<usize>,
+    dealer: PlayerId,
+    direction: Direction,
+    face_up: usize,
+    trump: Option<Trump>,
+    draws: usize,
+    open_draws: bool,
+}
+
+impl Dealing {
+    /// Creates a new `Dealing` with the player who deals the cards and the
+    /// size of the packet each player receives in every round. The dealer
+    /// receives their cards last and the number of players is the one of the
+    /// table they sit at. The cards are dealt clockwise, no card is turned
+    /// face up, there's no trump and nobody draws from the stock.
+    pub fn new(dealer: PlayerId, packets: Vec<usize>) -> Self {
+        Self {
+            packets,
+            dealer,
+            direction: Direction::default(),
+            face_up: 0,
+            trump: None,
+            draws: 0,
+            open_draws: false,
+        }
+    }
+
+    /// Sets the direction the cards are dealt in.
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the amount of cards to turn face up once every player received
+    /// their cards.
+    pub fn with_face_up(mut self, cards: usize) -> Self {
+        self.face_up = cards;
+        self
+    }
+
+    /// Sets how the trump suit is chosen.
+    pub fn with_trump(mut self, trump: Trump) -> Self {
+        self.trump = Some(trump);
+        self
+    }
+
+    /// Sets the amount of cards every player draws from the stock after each
+    /// trick, starting from the taker and following the order of play. The
+    /// cards turned face up lie under the stock, so they are drawn last. When
+    /// there aren't enough cards left for everyone, nobody draws.
+    pub fn with_draws(mut self, cards: usize) -> Self {
+        self.draws = cards;
+        self
+    }
+
+    /// Shows the cards drawn after each trick to every player, as in two
+    /// players tressette. By default they're only seen by whoever draws them.
+    pub fn with_open_draws(mut self) -> Self {
+        self.open_draws = true;
+        self
+    }
+
+    /// Returns the player who deals the cards.
+    pub fn dealer(&self) -> PlayerId {
+        self.dealer
+    }
+
+    /// Returns the direction the cards are dealt in.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the amount of cards turned face up.
+    pub fn face_up(&self) -> usize {
+        self.face_up
+    }
+
+    /// Returns how the trump suit is chosen, if there's one.
+    pub fn trump(&self) -> Option<Trump> {
+        self.trump
+    }
+
+    /// Returns the trump suit of a hand dealt this way, given the cards that
+    /// have been turned face up. Returns None if there's no trump, or if it
+    /// should have been turned face up but no card has been.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{cards::{ItalianCard, ItalianRank, Suit}, dealing::{Dealing, Trump}, hands::PlayerId};
+    ///
+    /// let dealing = Dealing::new(PlayerId::new(0, 2).unwrap(), vec![3])
+    ///     .with_face_up(1)
+    ///     .with_trump(Trump::FaceUp);
+    /// let face_up = [ItalianCard::new(ItalianRank::Seven, Suit::Clubs)];
+    ///
+    /// assert_eq!(dealing.reveal_trump(&face_up), Some(Suit::Clubs));
+    /// ```
+    pub fn reveal_trump<C: Suited>(&self, face_up: &[C]) -> Option<Suit> {
+        match self.trump? {
+            Trump::Fixed(suit) => Some(suit),
+            Trump::FaceUp => face_up.first().map(|c| c.suit()),
+        }
+    }
+
+    /// Returns the amount of cards every player draws after each trick.
+    pub fn draws(&self) -> usize {
+        self.draws
+    }
+
+    /// Returns true if the cards drawn are shown to every player.
+    pub fn open_draws(&self) -> bool {
+        self.open_draws
+    }
+
+    /// Returns the sizes of the packets dealt in each round.
+    pub fn packets(&self) -> &[usize] {
+        &self.packets
+    }
+
+    /// Returns the amount of cards each player receives.
+    pub fn cards_per_player(&self) -> usize {
+        self.packets.iter().sum()
+    }
+
+    /// Returns the amount of cards needed for this deal, face up cards
+    /// included.
+    pub fn cards_needed(&self) -> usize {
+        self.cards_per_player() * self.dealer.players() + self.face_up
+    }
+
+    /// Returns the order in which the players receive their packets in each
+    /// round.
+    pub fn order(&self) -> Vec<PlayerId> {
+        let mut seat = self.dealer;
+        (0..self.dealer.players())
+            .map(|_| {
+                self.advance(&mut seat);
+                seat
+            })
+            .collect()
+    }
+
+    /// Returns the player receiving each of the cards dealt to the players,
+    /// in the order they are drawn from the top of the deck. The cards turned
+    /// face up are drawn right after them.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{dealing::Dealing, hands::PlayerId};
+    ///
+    /// let dealing = Dealing::new(PlayerId::new(1, 2).unwrap(), vec![2, 1]);
+    /// let recipients: Vec<usize> = dealing.recipients().into_iter().map(|p| *p).collect();
+    ///
+    /// assert_eq!(recipients, vec![0, 0, 1, 1, 0, 1]);
+    /// ```
+    pub fn recipients(&self) -> Vec<PlayerId> {
+        let order = self.order();
+        self.packets
+            .iter()
+            .flat_map(|&packet| {
+                order
+                    .iter()
+                    .flat_map(move |&id| std::iter::repeat_n(id, packet))
+            })
+            .collect()
+    }
+
+    fn advance(&self, seat: &mut PlayerId) {
+        match self.direction {
+            Direction::Clockwise => seat.inc(),
+            Direction::Counterclockwise => seat.dec(),
+        }
+    }
+}
+
+/// The reasons why a deal might fail. When it fails, the deck and the players
+/// are left untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DealError {
+    /// The deck doesn't contain enough cards for the requested deal.
+    NotEnoughCards {
+        /// The cards needed for the deal.
+        needed: usize,
+        /// The cards left in the deck.
+        available: usize,
+    },
+    /// None of the players has the given id.
+    MissingPlayer(usize),
+    /// There are no players to deal the cards to.
+    NoPlayers,
+}
+
+impl Display for DealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DealError::NotEnoughCards { needed, available } => write!(
+                f,
+                "the deal needs {} cards, but the deck only has {}",
+                needed, available
+            ),
+            DealError::MissingPlayer(id) => write!(f, "there is no player with id {}", id),
+            DealError::NoPlayers => write!(f, "there are no players to deal to"),
+        }
+    }
+}
+
+impl std::error::Error for DealError {}
+
+impl<T: Card> Deck<T> {
+    /// Deals the cards from the top of the deck to the players, as described
+    /// by `dealing`, and returns the cards turned face up. The cards that
+    /// aren't dealt are left in the deck.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the deck doesn't have enough cards or if any of the players
+    /// meant to receive cards isn't in `players`.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{cards::Deck, dealing::Dealing, hands::{Player, PlayerId}};
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// let mut players: Vec<Player<TressetteRules>> = (0..4)
+    ///     .map(|i| Player::new(PlayerId::new(i, 4).unwrap()))
+    ///     .collect();
+    /// let mut deck = Deck::italian();
+    /// deck.shuffle();
+    ///
+    /// let dealing = Dealing::new(players[0].id(), vec![5, 5]);
+    /// let face_up = deck.deal(&mut players, &dealing).unwrap();
+    ///
+    /// assert!(face_up.is_empty());
+    /// assert!(deck.is_empty());
+    /// players.iter().for_each(|p| assert_eq!(p.hand().len(), 10));
+    /// ```
+    pub fn deal<G>(
+        &mut self,
+        players: &mut [Player<G>],
+        dealing: &Dealing,
+    ) -> Result<Vec<T>, DealError>
+    where
+        G: TrickTakingGame,
+        G::CardType: From<T>,
+    {
+        let needed = dealing.cards_needed();
+        if needed > self.len() {
+            return Err(DealError::NotEnoughCards {
+                needed,
+                available: self.len(),
+            });
+        }
+
+        let order = dealing.order();
+        let seats = order
+            .iter()
+            .map(|id| {
+                players
+                    .iter()
+                    .position(|p| p.id() == *id)
+                    .ok_or(DealError::MissingPlayer(**id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for &packet in dealing.packets() {
+            for &seat in &seats {
+                (0..packet)
+                    .filter_map(|_| self.draw())
+                    .for_each(|card| players[seat].give(card.into()));
+            }
+        }
+
+        Ok((0..dealing.face_up).filter_map(|_| self.draw()).collect())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        common::{
+            cards::{Deck, ItalianCard, ItalianRank, Suit},
+            hands::{Player, PlayerId},
+        },
+        tressette::{TressetteCard, TressetteRules},
+    };
+
+    use super::{DealError, Dealing, Direction, Trump};
+
+    fn players() -> Vec<Player<TressetteRules>> {
+        (0..4)
+            .map(|i| Player::new(PlayerId::new(i, 4).unwrap()))
+            .collect()
+    }
+
+    fn dealing(packets: Vec<usize>) -> Dealing {
+        Dealing::new(PlayerId::new(0, 4).unwrap(), packets)
+    }
+
+    #[test]
+    fn deals_from_the_player_after_the_dealer() {
+        let mut players = players();
+        let mut deck = Deck::italian();
+        let top = TressetteCard::from(deck[deck.len() - 1]);
+
+        let dealing = Dealing::new(PlayerId::new(2, 4).unwrap(), vec![1]);
+        deck.deal(&mut players, &dealing).unwrap();
+        assert_eq!(players[3].hand().iter().collect::<Vec<_>>(), vec![top]);
+
+        let mut players = self::players();
+        let mut deck = Deck::italian();
+        let dealing = dealing.with_direction(Direction::Counterclockwise);
+        deck.deal(&mut players, &dealing).unwrap();
+        assert_eq!(players[1].hand().iter().collect::<Vec<_>>(), vec![top]);
+    }
+
+    #[test]
+    fn face_up_cards_are_returned_and_the_rest_is_left() {
+        let mut players = players();
+        let mut deck = Deck::italian();
+
+        let face_up = deck
+            .deal(&mut players, &dealing(vec![3]).with_face_up(1))
+            .unwrap();
+
+        assert_eq!(face_up.len(), 1);
+        assert_eq!(deck.len(), 40 - 4 * 3 - 1);
+        players.iter().for_each(|p| assert_eq!(p.hand().len(), 3));
+    }
+
+    #[test]
+    fn not_enough_cards() {
+        let mut players = players();
+        let mut deck = Deck::italian();
+
+        let result = deck.deal(&mut players, &dealing(vec![11]));
+
+        assert_eq!(
+            result,
+            Err(DealError::NotEnoughCards {
+                needed: 44,
+                available: 40
+            })
+        );
+        assert_eq!(deck.len(), 40);
+        players.iter().for_each(|p| assert!(p.hand().is_empty()));
+    }
+
+    #[test]
+    fn missing_player() {
+        let mut players = players();
+        players.pop();
+        let mut deck = Deck::italian();
+
+        let result = deck.deal(&mut players, &dealing(vec![5, 5]));
+
+        assert_eq!(result, Err(DealError::MissingPlayer(3)));
+    }
+
+    #[test]
+    fn recipients_follow_the_deal() {
+        let mut players = players();
+        let mut deck = Deck::italian();
+        let dealing = dealing(vec![2, 3]).with_face_up(1);
+        let mut cards = deck.to_vec();
+
+        deck.deal(&mut players, &dealing).unwrap();
+
+        for id in dealing.recipients() {
+            let card = TressetteCard::from(cards.pop().unwrap());
+            assert!(players[*id].hand().contains(card));
+        }
+    }
+
+    #[test]
+    fn trump_is_revealed() {
+        let seven = ItalianCard::new(ItalianRank::Seven, Suit::Clubs);
+        let face_up = dealing(vec![3]).with_face_up(1).with_trump(Trump::FaceUp);
+        let fixed = dealing(vec![3]).with_trump(Trump::Fixed(Suit::Hearts));
+
+        assert_eq!(face_up.reveal_trump(&[seven]), Some(Suit::Clubs));
+        assert_eq!(face_up.reveal_trump::<ItalianCard>(&[]), None);
+        assert_eq!(fixed.reveal_trump(&[seven]), Some(Suit::Hearts));
+        assert_eq!(dealing(vec![3]).reveal_trump(&[seven]), None);
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt::Display;
+
+use super::{
+    auction::Bid,
+    card_set::CardSet,
+    cards::{Ranked, ShuffleSeed},
+    game::{Action, Match, MatchError, MatchRules},
+    hands::{Hand, PlayerId, Trick},
+    scoring::{HandScore, MatchScore, Outcome},
+};
+
+/// Something that happened during a `Match`. Every match keeps the events it
+/// produced in an append-only log, which is enough to reconstruct it together
+/// with the seed it has been created with, see `Match::replay`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "G::CardType: serde::Serialize, \
+                     <G::CardType as Ranked>::Rank: serde::Serialize, \
+                     G::Signal: serde::Serialize",
+        deserialize = "G::CardType: serde::Deserialize<'de>, \
+                       <G::CardType as Ranked>::Rank: serde::Deserialize<'de>, \
+                       G::Signal: serde::Deserialize<'de>"
+    ))
+)]
+pub enum Event<G>
+where
+    G: MatchRules,
+{
+    /// The cards of a new hand have been dealt.
+    Dealt {
+        /// The player who dealt the cards.
+        dealer: PlayerId,
+        /// The cards received by each player, ordered by `PlayerId`.
+        hands: Vec<CardSet<G::CardType>>,
+        /// The cards turned face up.
+        face_up: Vec<G::CardType>,
+    },
+    /// A player made a bid in the auction opening the hand.
+    Bid {
+        /// The player who made the bid.
+        player: PlayerId,
+        /// The bid made.
+        bid: Bid<<G::CardType as Ranked>::Rank>,
+    },
+    /// The winner of the auction called a card.
+    Called {
+        /// The player who called the card.
+        player: PlayerId,
+        /// The card called.
+        card: G::CardType,
+    },
+    /// A player declared a combination of cards they hold.
+    Declared {
+        /// The player who declared the cards.
+        player: PlayerId,
+        /// The cards declared.
+        cards: CardSet<G::CardType>,
+    },
+    /// A player played a card in the current trick.
+    CardPlayed {
+        /// The player who played the card.
+        player: PlayerId,
+        /// The card played.
+        card: G::CardType,
+        /// The signal made to their partner about the card, if any.
+        signal: Option<G::Signal>,
+    },
+    /// A player drew a card from the stock, or one of the cards turned face up
+    /// once the stock was over.
+    Drawn {
+        /// The player who drew the card.
+        player: PlayerId,
+        /// The card drawn.
+        card: G::CardType,
+    },
+    /// Every player played their card and the trick has been taken.
+    TrickTaken {
+        /// The trick, with its taker.
+        trick: Trick<G>,
+    },
+    /// Every trick of the hand has been played and the hand has been scored.
+    HandScored {
+        /// The hand played.
+        hand: Hand<G>,
+        /// The points made by each team in this hand.
+        score: HandScore,
+    },
+    /// The match is over.
+    MatchWon {
+        /// How the match ended.
+        outcome: Outcome,
+        /// The final score of the match.
+        score: MatchScore,
+    },
+}
+
+impl<G> Event<G>
+where
+    G: MatchRules,
+{
+    /// Returns the action of a player causing this event, or None if it has
+    /// been caused by another event, like a trick being taken once its last
+    /// card has been played.
+    pub fn action(&self) -> Option<Action<G>> {
+        match *self {
+            Event::Bid { player, bid } => Some(Action::Bid { player, bid }),
+            Event::Called { player, card } => Some(Action::Call { player, card }),
+            Event::CardPlayed {
+                player,
+                card,
+                signal,
+            } => Some(Action::Play {
+                player,
+                card,
+                signal,
+            }),
+            Event::Declared { player, cards } => Some(Action::Declare { player, cards }),
+            Event::Dealt { .. }
+            | Event::Drawn { .. }
+            | Event::TrickTaken { .. }
+            | Event::HandScored { .. }
+            | Event::MatchWon { .. } => None,
+        }
+    }
+}
+
+/// The reasons why a log of events can't be replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The log has fewer events than requested.
+    OutOfRange {
+        /// The amount of events requested.
+        requested: usize,
+        /// The amount of events in the log.
+        available: usize,
+    },
+    /// The events requested end in between the ones produced together by a
+    /// single action, such as playing the last card of a trick and taking
+    /// it, so no state of the match matches them.
+    InsideAction {
+        /// The amount of events requested.
+        requested: usize,
+        /// The amount of events before the action.
+        start: usize,
+        /// The amount of events once the action has been applied.
+        end: usize,
+    },
+    /// The event at this index isn't the one produced by the match, so the
+    /// log doesn't belong to a match played with these rules and seed.
+    Diverged(usize),
+    /// The action causing the event at `index` couldn't be applied.
+    Rejected {
+        /// The index of the event.
+        index: usize,
+        /// The reason why the action couldn't be applied.
+        error: MatchError,
+    },
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::OutOfRange {
+                requested,
+                available,
+            } => write!(
+                f,
+                "{} events were requested, but the log only has {}",
+                requested, available
+            ),
+            ReplayError::InsideAction {
+                requested,
+                start,
+                end,
+            } => write!(
+                f,
+                "{} events end in the middle of an action, which produced the events from {} to {}",
+                requested, start, end
+            ),
+            ReplayError::Diverged(index) => {
+                write!(f, "the match diverged from the log at event {}", index)
+            }
+            ReplayError::Rejected { index, error } => {
+                write!(f, "event {} couldn't be replayed: {}", index, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl<G> Match<G>
+where
+    G: MatchRules,
+{
+    /// Reconstructs the state of a match after the first `len` events of
+    /// `log`, by creating a new match with `rules` and `seed` and replaying
+    /// the actions of the players. Every event produced along the way is checked against
+    /// the log.
+    ///
+    /// The events caused by an action, like taking the trick or dealing the next
+    /// hand, are produced together with it, and the ones of the first deal
+    /// together with the match. The match returned has exactly `len` events,
+    /// so `len` can't fall in between them.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `log` doesn't have `len` events, if it isn't the log of a
+    /// match created with `rules` and `seed` or if `len` falls in between the
+    /// events produced by a single action.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{cards::ShuffleSeed, game::Match};
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// let seed = ShuffleSeed::from(42);
+    /// let mut tressette = Match::new(TressetteRules::default(), seed).unwrap();
+    /// for _ in 0..10 {
+    ///     let action = tressette.legal_moves()[0];
+    ///     tressette.apply(action).unwrap();
+    /// }
+    ///
+    /// let log = tressette.events();
+    /// let replayed = Match::replay(TressetteRules::default(), seed, log, log.len()).unwrap();
+    /// assert_eq!(replayed.events(), log);
+    /// assert_eq!(replayed.current_trick(), tressette.current_trick());
+    /// ```
+    pub fn replay(
+        rules: G,
+        seed: ShuffleSeed,
+        log: &[Event<G>],
+        len: usize,
+    ) -> Result<Self, ReplayError> {
+        let log = log.get(..len).ok_or(ReplayError::OutOfRange {
+            requested: len,
+            available: log.len(),
+        })?;
+        let mut replayed = Match::new(rules, seed).map_err(|e| ReplayError::Rejected {
+            index: 0,
+            error: e.into(),
+        })?;
+
+        // Only the events produced by the last action need to be compared,
+        // the previous ones already matched the log.
+        let mut checked = 0;
+        loop {
+            let events = replayed.events();
+            if let Some(offset) = events[checked..]
+                .iter()
+                .zip(&log[checked..])
+                .position(|(produced, logged)| produced != logged)
+            {
+                return Err(ReplayError::Diverged(checked + offset));
+            }
+
+            let index = events.len();
+            if index > len {
+                return Err(ReplayError::InsideAction {
+                    requested: len,
+                    start: checked,
+                    end: index,
+                });
+            }
+            checked = index;
+            let Some(event) = log.get(index) else {
+                return Ok(replayed);
+            };
+            let action = event.action().ok_or(ReplayError::Diverged(index))?;
+            replayed
+                .apply(action)
+                .map_err(|error| ReplayError::Rejected { index, error })?;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic, clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        common::{cards::ShuffleSeed, game::Match},
+        tressette::TressetteRules,
+    };
+
+    use super::{Event, ReplayError};
+
+    fn played(seed: ShuffleSeed, moves: usize) -> Match<TressetteRules> {
+        let mut tressette = Match::new(TressetteRules::default(), seed).unwrap();
+        for _ in 0..moves {
+            let action = tressette.legal_moves()[0];
+            tressette.apply(action).unwrap();
+        }
+        tressette
+    }
+
+    #[test]
+    fn every_prefix_of_the_log_can_be_replayed() {
+        let seed = ShuffleSeed::from(3);
+        let tressette = played(seed, 45);
+        let log = tressette.events();
+
+        assert!(matches!(log[0], Event::Dealt { .. }));
+        assert!(log.iter().any(|e| matches!(e, Event::HandScored { .. })));
+
+        for len in 0..=log.len() {
+            match Match::replay(TressetteRules::default(), seed, log, len) {
+                Ok(replayed) => assert_eq!(replayed.events(), &log[..len]),
+                Err(ReplayError::InsideAction { start, end, .. }) => {
+                    assert!((start..end).contains(&len));
+                    assert!(Match::replay(TressetteRules::default(), seed, log, end).is_ok());
+                }
+                Err(e) => panic!("{}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn replays_stop_in_between_actions() {
+        let seed = ShuffleSeed::from(3);
+        let tressette = played(seed, 4);
+        let log = tressette.events();
+        let taken = log
+            .iter()
+            .position(|e| matches!(e, Event::TrickTaken { .. }))
+            .unwrap();
+
+        // The trick is taken as soon as its last card is played.
+        assert_eq!(
+            Match::replay(TressetteRules::default(), seed, log, taken).unwrap_err(),
+            ReplayError::InsideAction {
+                requested: taken,
+                start: taken - 1,
+                end: taken + 1,
+            }
+        );
+        let replayed = Match::replay(TressetteRules::default(), seed, log, taken + 1).unwrap();
+        assert_eq!(replayed.events(), &log[..=taken]);
+        assert!(Match::replay(TressetteRules::default(), seed, log, 0).is_err());
+    }
+
+    #[test]
+    fn a_whole_match_is_logged() {
+        let mut tressette = played(ShuffleSeed::from(5), 0);
+        while !tressette.is_over() {
+            let action = tressette.legal_moves()[0];
+            tressette.apply(action).unwrap();
+        }
+
+        assert_eq!(
+            tressette.events().last(),
+            Some(&Event::MatchWon {
+                outcome: tressette.outcome().unwrap(),
+                score: tressette.score().clone(),
+            })
+        );
+        let replayed = Match::replay(
+            TressetteRules::default(),
+            ShuffleSeed::from(5),
+            tressette.events(),
+            tressette.events().len(),
+        )
+        .unwrap();
+        assert!(replayed.is_over());
+        assert_eq!(replayed.hands(), tressette.hands());
+    }
+
+    #[test]
+    fn tampered_logs_are_rejected() {
+        let seed = ShuffleSeed::from(3);
+        let tressette = played(seed, 6);
+        let log = tressette.events();
+
+        let other_seed = Match::replay(TressetteRules::default(), ShuffleSeed::from(4), log, 1);
+        assert_eq!(other_seed.unwrap_err(), ReplayError::Diverged(0));
+
+        let mut swapped = log.to_vec();
+        swapped.swap(1, 2);
+        let result = Match::replay(TressetteRules::default(), seed, &swapped, swapped.len());
+        assert!(matches!(
+            result,
+            Err(ReplayError::Rejected { index: 1, .. })
+        ));
+
+        let too_long = Match::replay(TressetteRules::default(), seed, log, log.len() + 1);
+        assert!(matches!(too_long, Err(ReplayError::OutOfRange { .. })));
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_logs_can_be_replayed() {
+        let seed = ShuffleSeed::from(9);
+        let tressette = played(seed, 50);
+
+        let json = serde_json::to_string(tressette.events()).unwrap();
+        let log: Vec<Event<TressetteRules>> = serde_json::from_str(&json).unwrap();
+        let replayed = Match::replay(TressetteRules::default(), seed, &log, log.len()).unwrap();
+
+        assert_eq!(replayed.events(), tressette.events());
+    }
+}
@@ -0,0 +1,422 @@
+use std::fmt::Display;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use super::cards::{Card, Deck, ShuffleSeed};
+
+const COMMITMENT_DOMAIN: &[u8] = b"shuftle/commitment";
+const SEED_DOMAIN: &[u8] = b"shuftle/seed";
+
+/// A hash of the seed chosen by the server. It's published before dealing, so
+/// that the server can't change its seed once it knows the entropy contributed
+/// by the players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Commitment([u8; 32]);
+
+impl Commitment {
+    /// Computes the commitment of a server seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{cards::ShuffleSeed, fairness::Commitment};
+    ///
+    /// let seed = ShuffleSeed::from(42);
+    /// assert_eq!(Commitment::of(&seed), Commitment::of(&seed));
+    /// assert_ne!(Commitment::of(&seed), Commitment::of(&ShuffleSeed::from(43)));
+    /// ```
+    pub fn of(server_seed: &ShuffleSeed) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(COMMITMENT_DOMAIN);
+        hasher.update(server_seed.as_bytes());
+        Self(hasher.finalize().into())
+    }
+
+    /// Creates a commitment from its raw bytes, as received from the server.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the raw bytes of the commitment.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Display for Commitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+/// Random bytes contributed by a player before the shuffle. As long as at
+/// least one player picks them honestly, the server can't predict the final
+/// permutation of the deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entropy([u8; 32]);
+
+impl Entropy {
+    /// Creates a contribution from its raw bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Generates a new contribution from the entropy of the operating system.
+    pub fn random() -> Self {
+        let mut bytes = [0; 32];
+        rand::rngs::OsRng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Returns the raw bytes of the contribution.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// Everything the server discloses once the hand is over: its own seed and
+/// the contributions of the players, in the order they were received.
+///
+/// The server builds this list itself, so the players must check that it
+/// holds exactly the contributions made at the table: `verify` and
+/// `reconstruct` do it for the ones they are given.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reveal {
+    server_seed: ShuffleSeed,
+    contributions: Vec<Entropy>,
+}
+
+impl Reveal {
+    /// Creates a reveal from the server seed and the contributions of the
+    /// players.
+    pub fn new(server_seed: ShuffleSeed, contributions: Vec<Entropy>) -> Self {
+        Self {
+            server_seed,
+            contributions,
+        }
+    }
+
+    /// Getter for the seed chosen by the server.
+    pub fn server_seed(&self) -> ShuffleSeed {
+        self.server_seed
+    }
+
+    /// Getter for the contributions of the players.
+    pub fn contributions(&self) -> &[Entropy] {
+        &self.contributions
+    }
+
+    /// Computes the seed the deck has been shuffled with: the hash of the
+    /// server seed followed by every contribution, sorted so that the server
+    /// can't pick among the orders it received them in.
+    pub fn shuffle_seed(&self) -> ShuffleSeed {
+        combine(&self.server_seed, &self.contributions)
+    }
+}
+
+fn combine(server_seed: &ShuffleSeed, contributions: &[Entropy]) -> ShuffleSeed {
+    let mut hasher = Sha256::new();
+    hasher.update(SEED_DOMAIN);
+    hasher.update(server_seed.as_bytes());
+    let mut sorted = contributions.to_vec();
+    sorted.sort_unstable();
+    sorted.iter().for_each(|c| hasher.update(c.as_bytes()));
+    ShuffleSeed::new(hasher.finalize().into())
+}
+
+/// Server side of the commit-reveal protocol. The dealer picks a secret seed,
+/// publishes its `Commitment`, collects the `Entropy` of the players, shuffles
+/// and finally discloses everything with a `Reveal`.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{cards::Deck, fairness::{self, Entropy, FairDealer}};
+///
+/// let mut dealer = FairDealer::new();
+/// // Published to the players before they contribute.
+/// let commitment = dealer.commitment();
+///
+/// // Every player sees the contributions made at the table.
+/// let contributions = [Entropy::random(), Entropy::random()];
+/// contributions.iter().for_each(|&c| dealer.contribute(c));
+///
+/// let mut deck = Deck::italian();
+/// dealer.shuffle(&mut deck);
+///
+/// // Once the hand is over, any client can check the deck wasn't stacked.
+/// let reveal = dealer.reveal();
+/// assert!(fairness::verify(&commitment, &reveal, &contributions, Deck::italian(), &deck).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FairDealer {
+    server_seed: ShuffleSeed,
+    contributions: Vec<Entropy>,
+}
+
+impl FairDealer {
+    /// Creates a dealer with a freshly generated secret seed.
+    pub fn new() -> Self {
+        Self::with_seed(ShuffleSeed::random())
+    }
+
+    /// Creates a dealer with the given secret seed.
+    pub fn with_seed(server_seed: ShuffleSeed) -> Self {
+        Self {
+            server_seed,
+            contributions: Vec::new(),
+        }
+    }
+
+    /// Returns the commitment to publish before dealing.
+    pub fn commitment(&self) -> Commitment {
+        Commitment::of(&self.server_seed)
+    }
+
+    /// Records the entropy contributed by a player.
+    pub fn contribute(&mut self, entropy: Entropy) {
+        self.contributions.push(entropy);
+    }
+
+    /// Shuffles the deck with the seed obtained by combining the server seed
+    /// with every contribution received so far.
+    pub fn shuffle<T: Card>(&self, deck: &mut Deck<T>) {
+        deck.shuffle_from_seed(combine(&self.server_seed, &self.contributions));
+    }
+
+    /// Discloses the server seed and the contributions, consuming the dealer.
+    pub fn reveal(self) -> Reveal {
+        Reveal::new(self.server_seed, self.contributions)
+    }
+}
+
+impl Default for FairDealer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The reasons why a shuffle might fail the verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FairnessError {
+    /// The revealed server seed doesn't match the published commitment.
+    CommitmentMismatch,
+    /// A contribution made at the table isn't among the revealed ones: the
+    /// server dropped or replaced it.
+    ContributionMissing,
+    /// A revealed contribution hasn't been made at the table: the server added
+    /// it to pick among different shuffles.
+    UnexpectedContribution,
+    /// Shuffling the deck with the revealed seed doesn't produce the cards
+    /// that have been dealt.
+    DeckMismatch,
+}
+
+impl Display for FairnessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FairnessError::CommitmentMismatch => {
+                write!(f, "the revealed seed doesn't match the commitment")
+            }
+            FairnessError::ContributionMissing => {
+                write!(f, "the revealed contributions leave out one of the table")
+            }
+            FairnessError::UnexpectedContribution => {
+                write!(
+                    f,
+                    "the revealed contributions include one not made at the table"
+                )
+            }
+            FairnessError::DeckMismatch => {
+                write!(f, "the revealed seed doesn't produce the dealt cards")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FairnessError {}
+
+/// Recomputes the deck the server should have dealt from, starting from the
+/// deck in its initial order. Fails if the reveal doesn't match the
+/// commitment published before dealing or if its contributions aren't
+/// exactly `contributions`, the ones every player made at the table, in any
+/// order.
+///
+/// # Errors
+///
+/// Returns `FairnessError::CommitmentMismatch` if the revealed server seed is
+/// not the one the server committed to, `FairnessError::ContributionMissing`
+/// if one of `contributions` isn't among the revealed ones and
+/// `FairnessError::UnexpectedContribution` if the server revealed one more.
+pub fn reconstruct<T: Card>(
+    commitment: &Commitment,
+    reveal: &Reveal,
+    contributions: &[Entropy],
+    mut initial: Deck<T>,
+) -> Result<Deck<T>, FairnessError> {
+    if Commitment::of(&reveal.server_seed) != *commitment {
+        return Err(FairnessError::CommitmentMismatch);
+    }
+    let mut revealed = reveal.contributions.clone();
+    for contribution in contributions {
+        let Some(index) = revealed.iter().position(|c| c == contribution) else {
+            return Err(FairnessError::ContributionMissing);
+        };
+        revealed.swap_remove(index);
+    }
+    if !revealed.is_empty() {
+        return Err(FairnessError::UnexpectedContribution);
+    }
+
+    initial.shuffle_from_seed(reveal.shuffle_seed());
+    Ok(initial)
+}
+
+/// Verifies that `dealt` is exactly the deck obtained by shuffling `initial`
+/// with the revealed seed, that such seed is the one the server committed to
+/// and that it has been combined with `contributions`, the ones made at the
+/// table, and nothing else.
+///
+/// # Errors
+///
+/// Returns the errors of `reconstruct`, and `FairnessError::DeckMismatch` if
+/// the dealt cards differ from the reconstructed deck.
+pub fn verify<T: Card>(
+    commitment: &Commitment,
+    reveal: &Reveal,
+    contributions: &[Entropy],
+    initial: Deck<T>,
+    dealt: &[T],
+) -> Result<(), FairnessError> {
+    let expected = reconstruct(commitment, reveal, contributions, initial)?;
+    if expected.as_slice() == dealt {
+        Ok(())
+    } else {
+        Err(FairnessError::DeckMismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::cards::{Deck, ShuffleSeed};
+
+    use super::{verify, Entropy, FairDealer, FairnessError, Reveal};
+
+    #[test]
+    fn tampered_seed_is_rejected() {
+        let mut dealer = FairDealer::with_seed(ShuffleSeed::from(1));
+        let commitment = dealer.commitment();
+        dealer.contribute(Entropy::new([7; 32]));
+        let mut deck = Deck::italian();
+        dealer.shuffle(&mut deck);
+
+        let reveal = Reveal::new(ShuffleSeed::from(2), dealer.reveal().contributions);
+
+        assert_eq!(
+            verify(
+                &commitment,
+                &reveal,
+                &[Entropy::new([7; 32])],
+                Deck::italian(),
+                &deck
+            ),
+            Err(FairnessError::CommitmentMismatch)
+        );
+    }
+
+    #[test]
+    fn stacked_deck_is_rejected() {
+        let mut dealer = FairDealer::with_seed(ShuffleSeed::from(1));
+        let commitment = dealer.commitment();
+        dealer.contribute(Entropy::new([7; 32]));
+        let mut deck = Deck::italian();
+        dealer.shuffle(&mut deck);
+        deck.swap(0, 1);
+
+        assert_eq!(
+            verify(
+                &commitment,
+                &dealer.reveal(),
+                &[Entropy::new([7; 32])],
+                Deck::italian(),
+                &deck
+            ),
+            Err(FairnessError::DeckMismatch)
+        );
+    }
+
+    #[test]
+    fn substituted_contribution_is_rejected() {
+        let mine = Entropy::new([7; 32]);
+        let mut dealer = FairDealer::with_seed(ShuffleSeed::from(1));
+        let commitment = dealer.commitment();
+        // The server shuffles with a contribution of its own in place of ours.
+        dealer.contribute(Entropy::new([9; 32]));
+        dealer.contribute(Entropy::new([8; 32]));
+        let mut deck = Deck::italian();
+        dealer.shuffle(&mut deck);
+
+        assert_eq!(
+            verify(
+                &commitment,
+                &dealer.reveal(),
+                &[mine],
+                Deck::italian(),
+                &deck
+            ),
+            Err(FairnessError::ContributionMissing)
+        );
+    }
+
+    #[test]
+    fn forged_contribution_is_rejected() {
+        let contributions = [Entropy::new([7; 32]), Entropy::new([8; 32])];
+        let mut dealer = FairDealer::with_seed(ShuffleSeed::from(1));
+        let commitment = dealer.commitment();
+        contributions.iter().for_each(|&c| dealer.contribute(c));
+        // The server adds a contribution of its own, chosen after seeing the
+        // ones of the players.
+        dealer.contribute(Entropy::new([9; 32]));
+        let mut deck = Deck::italian();
+        dealer.shuffle(&mut deck);
+
+        assert_eq!(
+            verify(
+                &commitment,
+                &dealer.reveal(),
+                &contributions,
+                Deck::italian(),
+                &deck
+            ),
+            Err(FairnessError::UnexpectedContribution)
+        );
+    }
+
+    #[test]
+    fn order_of_contributions_does_not_matter() {
+        let contributions = vec![Entropy::new([1; 32]), Entropy::new([2; 32])];
+        let reversed = contributions.iter().rev().copied().collect();
+
+        assert_eq!(
+            Reveal::new(ShuffleSeed::from(1), contributions).shuffle_seed(),
+            Reveal::new(ShuffleSeed::from(1), reversed).shuffle_seed()
+        );
+    }
+
+    #[test]
+    fn contributions_change_the_shuffle() {
+        let mut dealer1 = FairDealer::with_seed(ShuffleSeed::from(1));
+        dealer1.contribute(Entropy::new([1; 32]));
+        let mut dealer2 = FairDealer::with_seed(ShuffleSeed::from(1));
+        dealer2.contribute(Entropy::new([2; 32]));
+
+        let mut deck1 = Deck::italian();
+        dealer1.shuffle(&mut deck1);
+        let mut deck2 = Deck::italian();
+        dealer2.shuffle(&mut deck2);
+
+        assert_ne!(*deck1, *deck2);
+    }
+}
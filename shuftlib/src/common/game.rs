@@ -0,0 +1,1316 @@
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+};
+
+use rand_chacha::ChaCha20Rng;
+
+use super::{
+    auction::{Auction, AuctionError, Bid},
+    card_set::CardSet,
+    cards::{Deck, Ranked, ShuffleSeed},
+    dealing::{DealError, Dealing},
+    events::Event,
+    hands::{Hand, HandContext, OngoingHand, OngoingTrick, PlayError, Player, PlayerId, Teams},
+    scoring::{HandScore, MatchScore, Outcome, ScoreBreakdown, Scoring},
+};
+
+/// The rules needed to play a whole match of a trick taking game, on top of
+/// the ones needed to play a single trick: which deck is used, how it's dealt,
+/// who plays with whom, and how the hands are scored, see `Scoring`. Rules are
+/// plain values, so they can be copied along with the matches and logs using
+/// them.
+pub trait MatchRules: Scoring + Clone + Debug + PartialEq {
+    /// The signals a player can make to their partner about the card they are
+    /// playing, see `legal_signals`. Games without signals use `()`.
+    type Signal: Copy + Debug + Eq + Hash;
+
+    /// Returns the unshuffled deck every hand is dealt from.
+    fn deck(&self) -> Deck<Self::CardType>;
+
+    /// Describes how the cards of a hand are dealt by `dealer`.
+    fn dealing(&self, dealer: PlayerId) -> Dealing;
+
+    /// Returns the teams playing a hand, when it's dealt. By default every
+    /// player plays on their own.
+    fn teams(&self) -> Teams {
+        Teams::individual(self.players())
+    }
+
+    /// Returns true if every hand opens with an `Auction`, led by the first
+    /// player receiving cards. By default there's none.
+    fn auction(&self) -> bool {
+        false
+    }
+
+    /// Returns the bids the next player of `auction` is allowed to make. The
+    /// sides of the hand are decided by the auction: its winner is the
+    /// declarer and, if no card can be called, they play alone against
+    /// everyone else, see `HandContext::side_of`.
+    fn legal_bids(
+        &self,
+        _auction: &Auction<Self::CardType>,
+    ) -> Vec<Bid<<Self::CardType as Ranked>::Rank>> {
+        Vec::new()
+    }
+
+    /// Returns the cards the winner of `auction` is allowed to call. Whoever
+    /// plays the card called becomes the partner of the winner. By default no
+    /// card is called.
+    fn legal_calls(&self, _auction: &Auction<Self::CardType>) -> Vec<Self::CardType> {
+        Vec::new()
+    }
+
+    /// Returns the combinations of cards a player holding `hand` can declare,
+    /// besides the ones they already `declared` in this hand. Declarations
+    /// are made during the first trick of a hand, on their turn and before
+    /// playing their card, and everyone gets to see the cards declared. By
+    /// default nothing can be declared.
+    fn legal_declarations(
+        &self,
+        _hand: CardSet<Self::CardType>,
+        _declared: &[CardSet<Self::CardType>],
+    ) -> Vec<CardSet<Self::CardType>> {
+        Vec::new()
+    }
+
+    /// Returns the points made by declaring `cards`, which have already been
+    /// checked to be a legal declaration. They are added to the score of the
+    /// team of the player who declared them once the hand is over. By
+    /// default declarations make no points.
+    fn declaration_points(&self, _cards: CardSet<Self::CardType>) -> u32 {
+        0
+    }
+
+    /// Returns the signals a player holding `hand` can make to their partner
+    /// while playing `card` in `trick`, which has already been checked to be
+    /// a legal play. By default no signal can be made.
+    fn legal_signals(
+        &self,
+        _hand: CardSet<Self::CardType>,
+        _card: Self::CardType,
+        _trick: &OngoingTrick<Self>,
+    ) -> Vec<Self::Signal> {
+        Vec::new()
+    }
+}
+
+/// The moves a player can make during a `Match`.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "G::CardType: serde::Serialize, \
+                     <G::CardType as Ranked>::Rank: serde::Serialize, \
+                     G::Signal: serde::Serialize",
+        deserialize = "G::CardType: serde::Deserialize<'de>, \
+                       <G::CardType as Ranked>::Rank: serde::Deserialize<'de>, \
+                       G::Signal: serde::Deserialize<'de>"
+    ))
+)]
+pub enum Action<G>
+where
+    G: MatchRules,
+{
+    /// A player plays a card in the current trick, possibly making a signal
+    /// about it to their partner.
+    Play {
+        /// The player making the move.
+        player: PlayerId,
+        /// The card played.
+        card: G::CardType,
+        /// The signal made with the card, if any.
+        signal: Option<G::Signal>,
+    },
+    /// A player bids in the auction opening the hand.
+    Bid {
+        /// The player making the move.
+        player: PlayerId,
+        /// The bid made.
+        bid: Bid<<G::CardType as Ranked>::Rank>,
+    },
+    /// The winner of the auction calls the card choosing their partner.
+    Call {
+        /// The player making the move.
+        player: PlayerId,
+        /// The card called.
+        card: G::CardType,
+    },
+    /// A player declares a combination of cards they hold.
+    Declare {
+        /// The player making the move.
+        player: PlayerId,
+        /// The cards declared.
+        cards: CardSet<G::CardType>,
+    },
+}
+
+// The derives would require the rules themselves to be Copy and Hash, while
+// only the types they use for cards, ranks and signals need to be.
+impl<G> Clone for Action<G>
+where
+    G: MatchRules,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G> Copy for Action<G> where G: MatchRules {}
+
+impl<G> Hash for Action<G>
+where
+    G: MatchRules,
+    G::CardType: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Action::Play {
+                player,
+                card,
+                signal,
+            } => (player, card, signal).hash(state),
+            Action::Bid { player, bid } => (player, bid).hash(state),
+            Action::Call { player, card } => (player, card).hash(state),
+            Action::Declare { player, cards } => (player, cards).hash(state),
+        }
+    }
+}
+
+/// The reasons why a combination of cards can't be declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationError {
+    /// Declarations can only be made during the first trick of a hand.
+    Closed,
+    /// The rules don't allow to declare these cards, or they have already
+    /// been declared.
+    Illegal,
+}
+
+impl Display for DeclarationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeclarationError::Closed => {
+                write!(f, "declarations can only be made in the first trick")
+            }
+            DeclarationError::Illegal => write!(f, "the cards can't be declared"),
+        }
+    }
+}
+
+impl std::error::Error for DeclarationError {}
+
+/// The reasons why an `Action` can't be applied to a `Match`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchError {
+    /// The match is already over.
+    Over,
+    /// The card can't be played.
+    Play(PlayError),
+    /// The cards of a new hand couldn't be dealt.
+    Deal(DealError),
+    /// The bid or the call can't be made.
+    Auction(AuctionError),
+    /// The cards can't be declared.
+    Declaration(DeclarationError),
+    /// The rules don't allow to make this signal with this card, see
+    /// `MatchRules::legal_signals`.
+    IllegalSignal,
+}
+
+impl From<PlayError> for MatchError {
+    fn from(value: PlayError) -> Self {
+        MatchError::Play(value)
+    }
+}
+
+impl From<DealError> for MatchError {
+    fn from(value: DealError) -> Self {
+        MatchError::Deal(value)
+    }
+}
+
+impl From<AuctionError> for MatchError {
+    fn from(value: AuctionError) -> Self {
+        MatchError::Auction(value)
+    }
+}
+
+impl From<DeclarationError> for MatchError {
+    fn from(value: DeclarationError) -> Self {
+        MatchError::Declaration(value)
+    }
+}
+
+impl Display for MatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchError::Over => write!(f, "the match is over"),
+            MatchError::Play(e) => write!(f, "{}", e),
+            MatchError::Deal(e) => write!(f, "{}", e),
+            MatchError::Auction(e) => write!(f, "{}", e),
+            MatchError::Declaration(e) => write!(f, "{}", e),
+            MatchError::IllegalSignal => write!(f, "the signal isn't allowed with this card"),
+        }
+    }
+}
+
+impl std::error::Error for MatchError {}
+
+/// A whole match of a trick taking game, made of as many hands as needed for
+/// it to be over. It owns everything needed to play: the cards left in the
+/// deck, the players, the current trick, the hands already played and the
+/// score. The only way to move the match forward is `apply`, which enforces the
+/// rules of the game, while `legal_moves` lists the actions allowed.
+///
+/// The first trick of a hand is led by the first player receiving cards, every
+/// other trick by the taker of the previous one. In games with an auction, no
+/// card can be played until it's over, see `MatchRules::auction`; if everyone
+/// passes, the next player deals a new hand. If the rules say so, players
+/// draw from the stock after every trick, see `Dealing::with_draws`, and
+/// during the first trick they might declare combinations of cards, see
+/// `MatchRules::legal_declarations`. Cards can be played with a signal to the
+/// partner, if the rules allow it, see `MatchRules::legal_signals`. Once a
+/// hand is over it's scored and, unless the match is over, the next player
+/// deals a new one. The deck is shuffled by a generator seeded when the match
+/// is created, so the same seed always deals the same cards.
+///
+/// Practice games can take actions back with `undo` and apply them again with
+/// `redo`, once enabled with `with_undo`: the state before each of the last
+/// actions is kept, so that undoing it restores the order of the deck as well.
+/// Other matches keep no history, the events they log are enough to replay
+/// them.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{cards::ShuffleSeed, game::Match};
+/// use shuftlib::tressette::TressetteRules;
+///
+/// let rules = TressetteRules::default();
+/// let mut tressette = Match::new(rules, ShuffleSeed::from(42)).unwrap();
+///
+/// while !tressette.is_over() {
+///     let action = tressette.legal_moves()[0];
+///     tressette.apply(action).unwrap();
+/// }
+///
+/// assert!(tressette.outcome().is_some());
+/// assert!(tressette.legal_moves().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Match<G>
+where
+    G: MatchRules,
+{
+    rules: G,
+    rng: ChaCha20Rng,
+    dealer: PlayerId,
+    players: Vec<Player<G>>,
+    stock: Deck<G::CardType>,
+    face_up: Vec<G::CardType>,
+    hand: OngoingHand<G>,
+    declarations: Vec<(PlayerId, CardSet<G::CardType>)>,
+    hands: Vec<Hand<G>>,
+    teams: Teams,
+    score: MatchScore,
+    events: Vec<Event<G>>,
+    undo_limit: usize,
+    undo: VecDeque<Snapshot<G>>,
+    redo: Vec<Action<G>>,
+}
+
+/// The state of a `Match` before an action has been applied, restored when
+/// the action is undone or when it fails halfway, such as when the next hand
+/// can't be dealt. Players and the ongoing hand, with its trick, are
+/// plain values and are simply cloned. Hands and events are only ever
+/// appended, so their length is enough to restore them.
+#[derive(Debug, Clone)]
+struct Snapshot<G>
+where
+    G: MatchRules,
+{
+    rng: ChaCha20Rng,
+    dealer: PlayerId,
+    players: Vec<Player<G>>,
+    stock: Deck<G::CardType>,
+    face_up: Vec<G::CardType>,
+    hand: OngoingHand<G>,
+    declarations: Vec<(PlayerId, CardSet<G::CardType>)>,
+    teams: Teams,
+    score: MatchScore,
+    hands: usize,
+    events: usize,
+}
+
+/// What the next move of a `Match` is about.
+enum Phase<'a, G>
+where
+    G: MatchRules,
+{
+    /// The auction is going on.
+    Bidding(&'a Auction<G::CardType>),
+    /// The auction is over, and its winner has to call a card.
+    Calling(&'a Auction<G::CardType>),
+    /// Cards are being played.
+    Playing(&'a OngoingTrick<G>),
+}
+
+impl<G> Match<G>
+where
+    G: MatchRules,
+{
+    /// Creates a match and deals its first hand, dealt by the last player.
+    /// Every deck is shuffled by a generator seeded with `seed`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the rules have no players or describe a deal that can't be
+    /// made with their deck.
+    pub fn new(rules: G, seed: ShuffleSeed) -> Result<Self, DealError> {
+        let players = rules.players();
+        let dealer = players
+            .checked_sub(1)
+            .and_then(|last| rules.player(last))
+            .ok_or(DealError::NoPlayers)?;
+
+        let mut this = Self {
+            players: Vec::new(),
+            hand: OngoingHand::new(&rules),
+            rules,
+            rng: seed.rng(),
+            dealer,
+            stock: Deck::new(),
+            face_up: Vec::new(),
+            declarations: Vec::new(),
+            hands: Vec::new(),
+            teams: Teams::undecided(players, 0),
+            score: MatchScore::default(),
+            events: Vec::new(),
+            undo_limit: 0,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        };
+        this.deal()?;
+        Ok(this)
+    }
+
+    /// Keeps the state before each of the last `limit` actions, so that they
+    /// can be taken back with `undo`. Without it, nothing can be undone.
+    pub fn with_undo(mut self, limit: usize) -> Self {
+        self.undo_limit = limit;
+        self.undo.truncate(limit);
+        self
+    }
+
+    /// Returns the rules this match is played with.
+    pub fn rules(&self) -> &G {
+        &self.rules
+    }
+
+    /// Returns the player who dealt the current hand.
+    pub fn dealer(&self) -> PlayerId {
+        self.dealer
+    }
+
+    /// Returns the players, ordered by `PlayerId`.
+    pub fn players(&self) -> &[Player<G>] {
+        &self.players
+    }
+
+    /// Returns the cards left in the deck after dealing the current hand.
+    pub fn stock(&self) -> &Deck<G::CardType> {
+        &self.stock
+    }
+
+    /// Returns the cards turned face up when the current hand was dealt.
+    pub fn face_up(&self) -> &[G::CardType] {
+        &self.face_up
+    }
+
+    /// Returns the hand being played.
+    pub fn ongoing_hand(&self) -> &OngoingHand<G> {
+        &self.hand
+    }
+
+    /// Returns the trick being played, if the match isn't over.
+    pub fn current_trick(&self) -> Option<&OngoingTrick<G>> {
+        self.hand.current_trick.as_ref().filter(|_| !self.is_over())
+    }
+
+    /// Returns the combinations of cards declared so far in the current hand,
+    /// with the players who declared them, in order. See
+    /// `MatchRules::legal_declarations`.
+    pub fn declarations(&self) -> &[(PlayerId, CardSet<G::CardType>)] {
+        &self.declarations
+    }
+
+    /// Returns the hands already played, in order.
+    pub fn hands(&self) -> &[Hand<G>] {
+        &self.hands
+    }
+
+    /// Returns the context of the current hand, such as its trump suit.
+    pub fn context(&self) -> &HandContext {
+        self.hand.context()
+    }
+
+    /// Returns the auction opening the current hand, if the game has one.
+    pub fn auction(&self) -> Option<&Auction<G::CardType>> {
+        self.hand.auction()
+    }
+
+    /// Returns the teams playing the current hand.
+    pub fn teams(&self) -> &Teams {
+        &self.teams
+    }
+
+    /// Returns the score of the match.
+    pub fn score(&self) -> &MatchScore {
+        &self.score
+    }
+
+    /// Returns how the match ended, or None if it isn't over.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.rules.target().outcome(&self.score)
+    }
+
+    /// Returns every event happened since the match has been created, in
+    /// order.
+    pub fn events(&self) -> &[Event<G>] {
+        &self.events
+    }
+
+    /// Returns true if the match is over.
+    pub fn is_over(&self) -> bool {
+        self.outcome().is_some()
+    }
+
+    /// Returns the player who has to make the next move, if the match isn't
+    /// over: the next bidder during the auction, its winner when a card has to
+    /// be called, and the next player of the current trick otherwise.
+    pub fn next_to_play(&self) -> Option<PlayerId> {
+        match self.phase()? {
+            Phase::Bidding(auction) => auction.next_to_bid(),
+            Phase::Calling(auction) => auction.winner(),
+            Phase::Playing(trick) => Some(trick.next_to_play()),
+        }
+    }
+
+    /// Returns every action allowed to the player who has to make the next
+    /// move. It's empty once the match is over. Cards are listed once, played
+    /// without a signal: the signals allowed with each of them are returned
+    /// by `legal_signals`.
+    pub fn legal_moves(&self) -> Vec<Action<G>> {
+        let (Some(phase), Some(player)) = (self.phase(), self.next_to_play()) else {
+            return Vec::new();
+        };
+
+        match phase {
+            Phase::Bidding(auction) => self
+                .rules
+                .legal_bids(auction)
+                .into_iter()
+                .map(|bid| Action::Bid { player, bid })
+                .collect(),
+            Phase::Calling(auction) => self
+                .rules
+                .legal_calls(auction)
+                .into_iter()
+                .map(|card| Action::Call { player, card })
+                .collect(),
+            Phase::Playing(trick) => {
+                let Some(seat) = self.players.get(*player) else {
+                    return Vec::new();
+                };
+                seat.hand()
+                    .iter()
+                    .filter(|&card| self.rules.check_play(seat, card, trick).is_ok())
+                    .map(|card| Action::Play {
+                        player,
+                        card,
+                        signal: None,
+                    })
+                    .chain(
+                        self.legal_declarations(player)
+                            .into_iter()
+                            .map(|cards| Action::Declare { player, cards }),
+                    )
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the signals the player who has to play next can make while
+    /// playing `card`, see `MatchRules::legal_signals`. It's empty if they
+    /// can't play `card`.
+    pub fn legal_signals(&self, card: G::CardType) -> Vec<G::Signal> {
+        let (Some(Phase::Playing(trick)), Some(player)) = (self.phase(), self.next_to_play())
+        else {
+            return Vec::new();
+        };
+
+        match self.players.get(*player) {
+            Some(seat) if self.rules.check_play(seat, card, trick).is_ok() => {
+                self.rules.legal_signals(seat.hand(), card, trick)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Applies an action to the match and returns the events it caused. When
+    /// it completes a trick, the trick is added to the current hand, players
+    /// draw their cards, if any, and the taker leads the next one. When it
+    /// completes a hand, the hand is scored and, unless the match is over, a
+    /// new one is dealt.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the match is over or if the action breaks the rules of the
+    /// game. It can also fail if the next hand can't be dealt, which can only
+    /// happen if the rules deal a different amount of cards from one hand to
+    /// the other. Either way, the match is left untouched.
+    pub fn apply(&mut self, action: Action<G>) -> Result<&[Event<G>], MatchError> {
+        let events = self.act(action)?;
+        self.redo.clear();
+        Ok(&self.events[events..])
+    }
+
+    /// Takes back the last action applied, restoring the match exactly as it
+    /// was before it, deck order included, and returns it. The events it
+    /// caused are removed from the log. Returns None if there's nothing to
+    /// undo, which is always the case unless the match has been created
+    /// `with_undo`, and once the actions kept have all been undone.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{cards::ShuffleSeed, game::Match};
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// let mut tressette = Match::new(TressetteRules::default(), ShuffleSeed::from(1))
+    ///     .unwrap()
+    ///     .with_undo(10);
+    /// let action = tressette.legal_moves()[0];
+    /// tressette.apply(action).unwrap();
+    ///
+    /// assert_eq!(tressette.undo(), Some(action));
+    /// assert_eq!(tressette.undo(), None);
+    /// assert!(tressette.legal_moves().contains(&action));
+    ///
+    /// tressette.redo().unwrap();
+    /// assert!(!tressette.legal_moves().contains(&action));
+    /// ```
+    pub fn undo(&mut self) -> Option<Action<G>> {
+        let action = self.events.get(self.undo.back()?.events)?.action()?;
+        let snapshot = self.undo.pop_back()?;
+
+        self.restore(snapshot);
+        self.redo.push(action);
+        Some(action)
+    }
+
+    /// Applies again the last action taken back by `undo`, and returns the
+    /// events it caused. Returns None if there's nothing to redo, which is
+    /// also the case after applying a new action, or if the action can't be
+    /// applied again, in which case nothing is left to redo.
+    pub fn redo(&mut self) -> Option<&[Event<G>]> {
+        let action = self.redo.pop()?;
+        match self.act(action) {
+            Ok(events) => Some(&self.events[events..]),
+            Err(_) => {
+                self.redo.clear();
+                None
+            }
+        }
+    }
+
+    /// Undoes up to `actions` actions, and returns how many have been undone,
+    /// which can be less when fewer actions have been kept, see `with_undo`.
+    /// They can be applied again with `redo`.
+    pub fn rewind(&mut self, actions: usize) -> usize {
+        (0..actions).take_while(|_| self.undo().is_some()).count()
+    }
+
+    fn snapshot(&self) -> Snapshot<G> {
+        Snapshot {
+            rng: self.rng.clone(),
+            dealer: self.dealer,
+            players: self.players.clone(),
+            stock: self.stock.clone(),
+            face_up: self.face_up.clone(),
+            hand: self.hand.clone(),
+            declarations: self.declarations.clone(),
+            teams: self.teams.clone(),
+            score: self.score.clone(),
+            hands: self.hands.len(),
+            events: self.events.len(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot<G>) {
+        self.rng = snapshot.rng;
+        self.dealer = snapshot.dealer;
+        self.players = snapshot.players;
+        self.stock = snapshot.stock;
+        self.face_up = snapshot.face_up;
+        self.hand = snapshot.hand;
+        self.declarations = snapshot.declarations;
+        self.teams = snapshot.teams;
+        self.score = snapshot.score;
+        self.hands.truncate(snapshot.hands);
+        self.events.truncate(snapshot.events);
+    }
+
+    /// Returns what the next move is about, or None if the match is over.
+    fn phase(&self) -> Option<Phase<'_, G>> {
+        if self.is_over() {
+            return None;
+        }
+
+        match self.hand.auction() {
+            Some(auction) if !auction.is_over() => Some(Phase::Bidding(auction)),
+            Some(auction) if auction.called().is_none() && self.awaits_call(auction) => {
+                Some(Phase::Calling(auction))
+            }
+            _ => self.hand.current_trick.as_ref().map(Phase::Playing),
+        }
+    }
+
+    /// Returns true if the winner of `auction` can call a card.
+    fn awaits_call(&self, auction: &Auction<G::CardType>) -> bool {
+        auction.winner().is_some() && !self.rules.legal_calls(auction).is_empty()
+    }
+
+    /// Checks whether `action` can be applied to the match.
+    fn check(&self, action: Action<G>) -> Result<(), MatchError> {
+        let phase = self.phase().ok_or(MatchError::Over)?;
+        let expected = self.next_to_play().ok_or(MatchError::Over)?;
+
+        match (action, phase) {
+            (
+                Action::Play {
+                    player,
+                    card,
+                    signal,
+                },
+                Phase::Playing(trick),
+            ) => {
+                let seat = self
+                    .players
+                    .get(*player)
+                    .filter(|p| p.id() == player)
+                    .ok_or(PlayError::NotYourTurn { expected })?;
+                self.rules.check_play(seat, card, trick)?;
+                match signal {
+                    Some(signal)
+                        if !self
+                            .rules
+                            .legal_signals(seat.hand(), card, trick)
+                            .contains(&signal) =>
+                    {
+                        Err(MatchError::IllegalSignal)
+                    }
+                    _ => Ok(()),
+                }
+            }
+            (Action::Play { .. }, _) => Err(AuctionError::InProgress.into()),
+            (Action::Bid { player, .. } | Action::Call { player, .. }, _) if player != expected => {
+                Err(AuctionError::NotYourTurn { expected }.into())
+            }
+            (Action::Bid { bid, .. }, Phase::Bidding(auction)) => {
+                if self.rules.legal_bids(auction).contains(&bid) {
+                    Ok(())
+                } else {
+                    Err(AuctionError::IllegalBid.into())
+                }
+            }
+            (Action::Call { card, .. }, Phase::Calling(auction)) => {
+                if self.rules.legal_calls(auction).contains(&card) {
+                    Ok(())
+                } else {
+                    Err(AuctionError::IllegalCall.into())
+                }
+            }
+            (Action::Bid { .. } | Action::Call { .. }, _) => Err(AuctionError::Closed.into()),
+            (Action::Declare { player, .. }, Phase::Playing(_)) if player != expected => {
+                Err(PlayError::NotYourTurn { expected }.into())
+            }
+            (Action::Declare { .. }, Phase::Playing(_)) if self.hand.index != 0 => {
+                Err(DeclarationError::Closed.into())
+            }
+            (Action::Declare { player, cards }, Phase::Playing(_)) => {
+                if self.legal_declarations(player).contains(&cards) {
+                    Ok(())
+                } else {
+                    Err(DeclarationError::Illegal.into())
+                }
+            }
+            (Action::Declare { .. }, _) => Err(DeclarationError::Closed.into()),
+        }
+    }
+
+    /// Returns the combinations of cards `player` can declare, which is only
+    /// possible during the first trick of a hand.
+    fn legal_declarations(&self, player: PlayerId) -> Vec<CardSet<G::CardType>> {
+        let Some(seat) = self.players.get(*player).filter(|_| self.hand.index == 0) else {
+            return Vec::new();
+        };
+        let declared: Vec<_> = self
+            .declarations
+            .iter()
+            .filter(|(p, _)| *p == player)
+            .map(|&(_, cards)| cards)
+            .collect();
+        self.rules.legal_declarations(seat.hand(), &declared)
+    }
+
+    /// Applies an action and returns the index of the first event it caused.
+    /// If it fails, the match is restored to the state it was in before.
+    fn act(&mut self, action: Action<G>) -> Result<usize, MatchError> {
+        self.check(action)?;
+
+        let snapshot = self.snapshot();
+        let first_event = self.events.len();
+        let applied = match action {
+            Action::Play {
+                player,
+                card,
+                signal,
+            } => self.play(player, card, signal),
+            Action::Bid { player, bid } => self.bid(player, bid).map_err(MatchError::from),
+            Action::Call { player, card } => {
+                self.call(player, card);
+                Ok(())
+            }
+            Action::Declare { player, cards } => {
+                self.declare(player, cards);
+                Ok(())
+            }
+        };
+        if let Err(error) = applied {
+            self.restore(snapshot);
+            return Err(error);
+        }
+
+        if self.undo_limit > 0 {
+            if self.undo.len() == self.undo_limit {
+                self.undo.pop_front();
+            }
+            self.undo.push_back(snapshot);
+        }
+        Ok(first_event)
+    }
+
+    /// Makes a bid that has already been checked. When it closes the auction
+    /// its winner becomes the declarer, or a new hand is dealt if everyone
+    /// passed.
+    fn bid(
+        &mut self,
+        player: PlayerId,
+        bid: Bid<<G::CardType as Ranked>::Rank>,
+    ) -> Result<(), DealError> {
+        let Some(auction) = self.hand.auction.as_mut() else {
+            return Ok(());
+        };
+        auction.bid(player, bid);
+        self.events.push(Event::Bid { player, bid });
+        if !auction.is_over() {
+            return Ok(());
+        }
+
+        let auction = auction.clone();
+        let Some(winner) = auction.winner() else {
+            self.dealer.inc();
+            return self.deal();
+        };
+        // The winner is the declarer as soon as the auction is over, even if
+        // the trump is only known once they call a card.
+        if self.awaits_call(&auction) {
+            self.start_contract(auction.context());
+        } else {
+            self.start_contract(auction.context().with_partner(winner));
+        }
+        Ok(())
+    }
+
+    /// Calls a card that has already been checked.
+    fn call(&mut self, player: PlayerId, card: G::CardType) {
+        let Some(auction) = self.hand.auction.as_mut() else {
+            return;
+        };
+        auction.call(card);
+        self.events.push(Event::Called { player, card });
+
+        let context = auction.context();
+        self.start_contract(context);
+    }
+
+    /// Declares cards that have already been checked.
+    fn declare(&mut self, player: PlayerId, cards: CardSet<G::CardType>) {
+        self.declarations.push((player, cards));
+        self.events.push(Event::Declared { player, cards });
+    }
+
+    /// Sets the context decided by the auction on the hand and its first
+    /// trick.
+    fn start_contract(&mut self, context: HandContext) {
+        self.hand.context = context;
+        self.hand.current_trick = self
+            .hand
+            .current_trick
+            .take()
+            .map(|t| t.with_context(context));
+    }
+
+    /// Plays a card whose move has already been checked, together with its
+    /// signal.
+    fn play(
+        &mut self,
+        player: PlayerId,
+        card: G::CardType,
+        signal: Option<G::Signal>,
+    ) -> Result<(), MatchError> {
+        let (Some(trick), Some(seat)) = (
+            self.hand.current_trick.as_mut(),
+            self.players.get_mut(*player),
+        ) else {
+            return Err(MatchError::Over);
+        };
+
+        self.rules.play(seat, card, trick)?;
+        let complete = trick.is_complete();
+        self.events.push(Event::CardPlayed {
+            player,
+            card,
+            signal,
+        });
+        // Playing the card called reveals the partner of the declarer.
+        if self.hand.auction().and_then(|a| a.called()) == Some(card) {
+            self.hand.context = self.hand.context.with_partner(player);
+        }
+        if complete {
+            self.end_trick()?;
+        }
+
+        Ok(())
+    }
+
+    fn end_trick(&mut self) -> Result<(), DealError> {
+        let Some(trick) = self
+            .hand
+            .current_trick
+            .take()
+            .and_then(|t| t.finish(&self.rules))
+        else {
+            return Ok(());
+        };
+
+        let taker = trick.taker();
+        self.events.push(Event::TrickTaken {
+            trick: trick.clone(),
+        });
+        self.hand.add(trick, self.hand.index);
+        self.hand.index += 1;
+        if self.hand.index < self.hand.tricks.len() {
+            self.draw(taker);
+            self.hand.current_trick =
+                OngoingTrick::new(&self.rules, taker).map(|t| t.with_context(self.hand.context));
+            return Ok(());
+        }
+
+        let hand = std::mem::replace(&mut self.hand, OngoingHand::new(&self.rules));
+        if let Some(hand) = hand.finish() {
+            let points = self.score_declarations(self.rules.score_hand(&hand, &self.teams));
+            self.score.add(points.clone());
+            self.events.push(Event::HandScored {
+                hand: hand.clone(),
+                score: points,
+            });
+            self.hands.push(hand);
+        }
+
+        if let Some(outcome) = self.outcome() {
+            self.events.push(Event::MatchWon {
+                outcome,
+                score: self.score.clone(),
+            });
+            return Ok(());
+        }
+        self.dealer.inc();
+        self.deal()
+    }
+
+    /// Adds the points of the declarations made in the hand to the score of
+    /// the teams of the players who made them.
+    fn score_declarations(&self, score: HandScore) -> HandScore {
+        if self.declarations.is_empty() {
+            return score;
+        }
+
+        let mut teams = score.teams().to_vec();
+        teams.resize(
+            teams.len().max(self.teams.count()),
+            ScoreBreakdown::default(),
+        );
+        for &(player, cards) in &self.declarations {
+            if let Some(team) = self
+                .teams
+                .team_of(player)
+                .and_then(|team| teams.get_mut(*team))
+            {
+                let points = team.declarations() + self.rules.declaration_points(cards);
+                *team = team.with_declarations(points);
+            }
+        }
+        HandScore::new(teams)
+    }
+
+    /// Lets every player draw from the stock, starting from `taker`, and then
+    /// from the cards turned face up.
+    fn draw(&mut self, taker: PlayerId) {
+        let draws = self.rules.dealing(self.dealer).draws() * self.players.len();
+        if draws == 0 || self.stock.len() + self.face_up.len() < draws {
+            return;
+        }
+
+        let mut player = taker;
+        for _ in 0..draws {
+            let card = match self.stock.draw() {
+                Some(card) => card,
+                None if !self.face_up.is_empty() => self.face_up.remove(0),
+                None => return,
+            };
+            if let Some(seat) = self.players.get_mut(*player) {
+                seat.give(card);
+                self.events.push(Event::Drawn { player, card });
+            }
+            player.inc();
+        }
+    }
+
+    fn deal(&mut self) -> Result<(), DealError> {
+        let dealing = self.rules.dealing(self.dealer);
+        let mut deck = self.rules.deck();
+        deck.shuffle_with(&mut self.rng);
+        let mut players: Vec<Player<G>> = (0..self.rules.players())
+            .filter_map(|i| self.rules.player(i))
+            .map(Player::new)
+            .collect();
+
+        self.face_up = deck.deal(&mut players, &dealing)?;
+        self.events.push(Event::Dealt {
+            dealer: self.dealer,
+            hands: players.iter().map(|p| p.hand()).collect(),
+            face_up: self.face_up.clone(),
+        });
+        self.players = players;
+        self.declarations.clear();
+        self.stock = deck;
+        self.teams = self.rules.teams();
+        let mut context = HandContext::default();
+        if let Some(trump) = dealing.reveal_trump(&self.face_up) {
+            context = context.with_trump(trump);
+        }
+        let mut hand = OngoingHand::new(&self.rules).with_context(context);
+        let leader = dealing.order().first().copied();
+        if let Some(leader) = leader.filter(|_| self.rules.auction()) {
+            hand = hand.with_auction(Auction::new(leader));
+        }
+        hand.current_trick = leader
+            .and_then(|leader| OngoingTrick::new(&self.rules, leader))
+            .map(|t| t.with_context(context));
+        self.hand = hand;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::common::{
+        cards::{Deck, ItalianCard, ItalianRank, ShuffleSeed, Suit},
+        dealing::{DealError, Dealing},
+        hands::{Hand, HandContext, PlayerId, Teams, TrickTakingGame},
+        scoring::{HandScore, ScoreBreakdown, Scoring, Target},
+    };
+
+    use super::{Action, Match, MatchError, MatchRules};
+
+    /// A game whose tricks are always taken by the player with id 0. It's
+    /// played by a variable number of players.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct TestGame {
+        players: usize,
+    }
+
+    impl TrickTakingGame for TestGame {
+        type CardType = ItalianCard;
+
+        fn players(&self) -> usize {
+            self.players
+        }
+
+        fn tricks(&self) -> usize {
+            10
+        }
+
+        fn determine_taker(
+            &self,
+            _cards: &[Self::CardType],
+            _first_to_play: PlayerId,
+            _context: &HandContext,
+        ) -> PlayerId {
+            self.player(0).unwrap()
+        }
+    }
+
+    /// Every player receives all of their cards at once and the match is over
+    /// after 3 hands.
+    impl MatchRules for TestGame {
+        type Signal = ();
+
+        fn deck(&self) -> Deck<ItalianCard> {
+            Deck::italian()
+        }
+
+        fn dealing(&self, dealer: PlayerId) -> Dealing {
+            Dealing::new(dealer, vec![self.tricks()])
+        }
+    }
+
+    /// Player 0 makes a point every hand.
+    impl Scoring for TestGame {
+        fn score_hand(&self, _hand: &Hand<Self>, _teams: &Teams) -> HandScore {
+            HandScore::new(vec![ScoreBreakdown::default().with_cards(1)])
+        }
+
+        fn target(&self) -> Target {
+            Target::new(3)
+        }
+    }
+
+    /// A `TestGame` of 4 players where only the first dealer deals the cards
+    /// right: the others need more cards than the deck has.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct MisdealGame(TestGame);
+
+    impl TrickTakingGame for MisdealGame {
+        type CardType = ItalianCard;
+
+        fn players(&self) -> usize {
+            self.0.players()
+        }
+
+        fn tricks(&self) -> usize {
+            self.0.tricks()
+        }
+
+        fn determine_taker(
+            &self,
+            cards: &[Self::CardType],
+            first_to_play: PlayerId,
+            context: &HandContext,
+        ) -> PlayerId {
+            self.0.determine_taker(cards, first_to_play, context)
+        }
+    }
+
+    impl MatchRules for MisdealGame {
+        type Signal = ();
+
+        fn deck(&self) -> Deck<ItalianCard> {
+            self.0.deck()
+        }
+
+        fn dealing(&self, dealer: PlayerId) -> Dealing {
+            let extra = usize::from(*dealer != self.players() - 1);
+            Dealing::new(dealer, vec![self.tricks() + extra])
+        }
+    }
+
+    impl Scoring for MisdealGame {
+        fn score_hand(&self, _hand: &Hand<Self>, _teams: &Teams) -> HandScore {
+            HandScore::new(vec![ScoreBreakdown::default().with_cards(1)])
+        }
+
+        fn target(&self) -> Target {
+            Target::new(3)
+        }
+    }
+
+    #[test]
+    fn failed_actions_leave_the_match_untouched() {
+        let game = MisdealGame(TestGame { players: 4 });
+        let mut test_match = Match::new(game, ShuffleSeed::from(1)).unwrap().with_undo(1);
+        let last_card = |m: &Match<MisdealGame>| {
+            let played = m
+                .current_trick()
+                .map_or(0, |t| t.cards().iter().flatten().count());
+            m.ongoing_hand().index() + 1 == game.tricks() && played + 1 == game.players()
+        };
+        while !last_card(&test_match) {
+            let action = test_match.legal_moves()[0];
+            test_match.apply(action).unwrap();
+        }
+        let before = test_match.clone();
+
+        // The last card of the hand is played, but the next one can't be dealt.
+        let action = test_match.legal_moves()[0];
+        assert!(matches!(
+            test_match.apply(action),
+            Err(MatchError::Deal(DealError::NotEnoughCards { .. }))
+        ));
+        assert_eq!(test_match.events(), before.events());
+        assert_eq!(test_match.hands(), before.hands());
+        for (player, seat) in test_match.players().iter().zip(before.players()) {
+            assert_eq!(player.hand(), seat.hand());
+        }
+        assert_eq!(test_match.ongoing_hand(), before.ongoing_hand());
+        assert_eq!(test_match.score(), before.score());
+        assert_eq!(test_match.legal_moves(), before.legal_moves());
+
+        // The last action applied can still be undone.
+        assert!(test_match.undo().is_some());
+    }
+
+    proptest! {
+        #[test]
+        fn match_is_played_until_it_is_over(players in 2..=4usize, seed: u64, choices in prop::collection::vec(any::<prop::sample::Index>(), 120)) {
+            let game = TestGame { players };
+            let mut test_match = Match::new(game, ShuffleSeed::from(seed)).unwrap();
+            let mut choices = choices.iter().cycle();
+            let mut dealer = game.player(players - 1).unwrap();
+
+            prop_assert_eq!(test_match.dealer(), dealer);
+            while !test_match.is_over() {
+                let hands = test_match.hands().len();
+                let index = test_match.ongoing_hand().index();
+                let legal_moves = test_match.legal_moves();
+                let action = *choices.next().unwrap().get(&legal_moves);
+                test_match.apply(action).unwrap();
+
+                if test_match.is_over() {
+                    break;
+                }
+                if test_match.hands().len() > hands {
+                    // A new hand is dealt by the next player, and led by the
+                    // one after them.
+                    dealer.inc();
+                    let mut leader = dealer;
+                    leader.inc();
+                    prop_assert_eq!(test_match.dealer(), dealer);
+                    prop_assert_eq!(test_match.next_to_play(), Some(leader));
+                    test_match.players().iter().for_each(|p| assert_eq!(p.hand().len(), game.tricks()));
+                } else if test_match.ongoing_hand().index() > index {
+                    // The taker of the previous trick leads.
+                    prop_assert_eq!(test_match.next_to_play(), game.player(0));
+                }
+            }
+
+            prop_assert_eq!(test_match.hands().len(), 3);
+            prop_assert!(test_match.legal_moves().is_empty());
+            prop_assert!(test_match.next_to_play().is_none());
+            let player = game.player(0).unwrap();
+            let card = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+            prop_assert_eq!(test_match.apply(Action::Play { player, card, signal: None }), Err(MatchError::Over));
+        }
+
+        #[test]
+        fn tables_without_players_cant_play(seed: u64) {
+            let game = TestGame { players: 0 };
+
+            prop_assert_eq!(Match::new(game, ShuffleSeed::from(seed)).err(), Some(DealError::NoPlayers));
+        }
+
+        #[test]
+        fn same_seed_same_match(seed: u64) {
+            let game = TestGame { players: 4 };
+            let first = Match::new(game, ShuffleSeed::from(seed)).unwrap();
+            let second = Match::new(game, ShuffleSeed::from(seed)).unwrap();
+
+            prop_assert_eq!(first.legal_moves(), second.legal_moves());
+        }
+
+        #[test]
+        fn undone_actions_restore_the_match(players in 2..=4usize, seed: u64, moves in 1..100usize, back in 1..100usize, choices in prop::collection::vec(any::<prop::sample::Index>(), 100)) {
+            let game = TestGame { players };
+            let mut test_match = Match::new(game, ShuffleSeed::from(seed)).unwrap().with_undo(100);
+            let mut history = vec![test_match.clone()];
+            for choice in choices.iter().take(moves) {
+                if test_match.is_over() {
+                    break;
+                }
+                let action = *choice.get(&test_match.legal_moves());
+                test_match.apply(action).unwrap();
+                history.push(test_match.clone());
+            }
+
+            let undone = test_match.rewind(back);
+            prop_assert_eq!(undone, back.min(history.len() - 1));
+            assert_same_state(&test_match, &history[history.len() - 1 - undone]);
+
+            (0..undone).for_each(|_| assert!(test_match.redo().is_some()));
+            prop_assert!(test_match.redo().is_none());
+            assert_same_state(&test_match, &history[history.len() - 1]);
+
+            // Applying a new action drops the actions that could be redone.
+            if test_match.undo().is_some() {
+                let action = test_match.legal_moves()[0];
+                test_match.apply(action).unwrap();
+                prop_assert!(test_match.redo().is_none());
+            }
+        }
+
+        #[test]
+        fn only_the_last_actions_are_undone(seed: u64, limit in 0..10usize) {
+            let game = TestGame { players: 4 };
+            let mut test_match = Match::new(game, ShuffleSeed::from(seed)).unwrap().with_undo(limit);
+            let mut history = vec![test_match.clone()];
+            for _ in 0..20 {
+                let action = test_match.legal_moves()[0];
+                test_match.apply(action).unwrap();
+                history.push(test_match.clone());
+            }
+
+            prop_assert_eq!(test_match.rewind(20), limit);
+            assert_same_state(&test_match, &history[20 - limit]);
+
+            let mut untracked = Match::new(game, ShuffleSeed::from(seed)).unwrap();
+            let action = untracked.legal_moves()[0];
+            untracked.apply(action).unwrap();
+            prop_assert!(untracked.undo().is_none());
+        }
+
+    }
+
+    fn assert_same_state(a: &Match<TestGame>, b: &Match<TestGame>) {
+        let hands = |m: &Match<TestGame>| {
+            m.players()
+                .iter()
+                .map(|p| (p.id(), p.hand()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(a.events(), b.events());
+        assert_eq!(a.hands(), b.hands());
+        assert_eq!(hands(a), hands(b));
+        assert_eq!(a.stock(), b.stock());
+        assert_eq!(a.face_up(), b.face_up());
+        assert_eq!(a.ongoing_hand(), b.ongoing_hand());
+        assert_eq!(a.score(), b.score());
+        assert_eq!(a.dealer(), b.dealer());
+
+        // The deck of the next hands is shuffled the same way.
+        let (mut a, mut b) = (a.clone(), b.clone());
+        while !a.is_over() {
+            let action = a.legal_moves()[0];
+            a.apply(action).unwrap();
+            b.apply(action).unwrap();
+        }
+        assert_eq!(a.events(), b.events());
+    }
+}
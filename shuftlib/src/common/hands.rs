@@ -1,50 +1,203 @@
-use std::{fmt::Display, ops::Deref};
+use std::{
+    fmt::{Debug, Display},
+    ops::Deref,
+    str::FromStr,
+};
 
-use anyhow::bail;
+use super::{
+    auction::Auction,
+    card_set::CardSet,
+    cards::{parse_cards, IndexedCard, ParseCardError, ParseCardsError, Ranked, Suit, Suited},
+};
 
-use super::cards::Card;
+/// Whole matches, played from the first deal to the end by `Match`, are kept
+/// in `common::game` along with their rules and actions, and can be used from
+/// here as well.
+pub use super::game::{Action, Match, MatchError, MatchRules};
 
-/// Many of the types contained in  this module are generic over certain
-/// constants related to the game. This trait is the summary of these
-/// constraints.
-pub trait TrickTakingGame {
+/// Many of the types contained in  this module are generic over the rules of
+/// the game being played. This trait is the summary of these rules. A value of
+/// this type describes a single table, so the number of players and tricks can
+/// be chosen when the table is created.
+pub trait TrickTakingGame: Sized {
     /// Define the type of card that's going to be used in this game.
-    type CardType: Card;
+    type CardType: IndexedCard + Suited + Ranked;
+
     /// Every game has a fixed number of players defined by the rules of the
     /// game or, anyway, before starting it.
-    const PLAYERS: usize;
+    fn players(&self) -> usize;
+
     /// Usually trick taking games have a fixed number of "turns" for each
     /// player. These "turns" are called tricks
-    const TRICKS: usize;
+    fn tricks(&self) -> usize;
 
     /// Every trick taking game has some logic to determine the winner (or
     /// taker) of the trick. The taker is generally determined by the cards that
     /// have been played and it can depend by the order in which the players
-    /// played their cards.
+    /// played their cards. `cards` contains one card for each player, ordered
+    /// by `PlayerId`, and `context` is the context of the hand the trick
+    /// belongs to, which holds the trump suit, if any.
     fn determine_taker(
-        cards: &[Self::CardType; Self::PLAYERS],
-        first_to_play: PlayerId<{ Self::PLAYERS }>,
-    ) -> PlayerId<{ Self::PLAYERS }>;
+        &self,
+        cards: &[Self::CardType],
+        first_to_play: PlayerId,
+        context: &HandContext,
+    ) -> PlayerId;
+
+    /// Returns the id of a player sitting at this table, or None if `id` is
+    /// not in the range 0..`players()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::hands::{PlayerId, TrickTakingGame};
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// let rules = TressetteRules::default();
+    /// assert_eq!(rules.player(3), PlayerId::new(3, 4));
+    /// assert!(rules.player(4).is_none());
+    /// ```
+    fn player(&self, id: usize) -> Option<PlayerId> {
+        PlayerId::new(id, self.players())
+    }
+
+    /// Checks the rules of the game restricting which of the cards held by a
+    /// player can be played in the current trick, such as having to follow
+    /// suit. `card` is guaranteed to be in `hand`. By default every card in
+    /// the hand can be played.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the rule `card` breaks, if any.
+    fn check_card(
+        &self,
+        _hand: CardSet<Self::CardType>,
+        _card: Self::CardType,
+        _trick: &OngoingTrick<Self>,
+    ) -> Result<(), PlayError> {
+        Ok(())
+    }
+
+    /// Checks whether `player` is allowed to play `card` in `trick`: the
+    /// trick must still be going on, it must be the turn of `player`, the card
+    /// must be in their hand and it must not break any rule of the game, as
+    /// defined by `check_card`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the first of the checks above that isn't satisfied.
+    fn check_play(
+        &self,
+        player: &Player<Self>,
+        card: Self::CardType,
+        trick: &OngoingTrick<Self>,
+    ) -> Result<(), PlayError> {
+        if trick.is_complete() {
+            return Err(PlayError::TrickComplete);
+        }
+        if player.id() != trick.next_to_play() {
+            return Err(PlayError::NotYourTurn {
+                expected: trick.next_to_play(),
+            });
+        }
+        if !player.hand().contains(card) {
+            return Err(PlayError::CardNotInHand);
+        }
+
+        self.check_card(player.hand(), card, trick)
+    }
+
+    /// Moves `card` from the hand of `player` to `trick`, if the move is
+    /// allowed by `check_play`. Nothing is changed otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the move isn't allowed, see `check_play`.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{cards::{ItalianRank, Suit}, hands::{OngoingTrick, PlayError, Player, TrickTakingGame}};
+    /// use shuftlib::tressette::{TressetteCard, TressetteRules};
+    ///
+    /// let rules = TressetteRules::default();
+    /// let mut player = Player::new(rules.player(0).unwrap());
+    /// let ace = TressetteCard::new(ItalianRank::Ace, Suit::Hearts);
+    /// player.give(ace);
+    /// let mut trick = OngoingTrick::new(&rules, player.id()).unwrap();
+    ///
+    /// let two = TressetteCard::new(ItalianRank::Two, Suit::Hearts);
+    /// assert_eq!(rules.play(&mut player, two, &mut trick), Err(PlayError::CardNotInHand));
+    /// assert_eq!(rules.play(&mut player, ace, &mut trick), Ok(()));
+    /// assert!(player.hand().is_empty());
+    /// assert_eq!(trick[0], Some(ace));
+    /// ```
+    fn play(
+        &self,
+        player: &mut Player<Self>,
+        card: Self::CardType,
+        trick: &mut OngoingTrick<Self>,
+    ) -> Result<(), PlayError> {
+        self.check_play(player, card, trick)?;
+        trick.play(card)?;
+        player.remove(card);
+        Ok(())
+    }
+}
+
+/// The reasons why a card can't be played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayError {
+    /// Another player is expected to play.
+    NotYourTurn {
+        /// The player whose turn it is.
+        expected: PlayerId,
+    },
+    /// The player doesn't hold the card.
+    CardNotInHand,
+    /// The player holds a card of the leading suit, so they have to play one.
+    MustFollowSuit,
+    /// Every player already played their card in the trick.
+    TrickComplete,
+}
+
+impl Display for PlayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayError::NotYourTurn { expected } => {
+                write!(f, "it's the turn of player {}", expected)
+            }
+            PlayError::CardNotInHand => write!(f, "the card isn't in the hand of the player"),
+            PlayError::MustFollowSuit => write!(f, "a card of the leading suit must be played"),
+            PlayError::TrickComplete => write!(f, "every player already played in this trick"),
+        }
+    }
 }
 
+impl std::error::Error for PlayError {}
+
 /// Represents a player of a game. This type is generic over the type of the
 /// card used for the specific game and over the number of players of such game.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "G::CardType: serde::Serialize",
+        deserialize = "G::CardType: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Player<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     /// The cards traditionally held in the hand by the player.
-    hand: Vec<G::CardType>,
+    hand: CardSet<G::CardType>,
     /// The ID of this player. This is used to determine their turn to play.
-    id: PlayerId<{ G::PLAYERS }>,
+    id: PlayerId,
 }
 
 impl<G> Player<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     /// Adds a card to the hand of the player.
     ///
@@ -54,7 +207,7 @@ where
     /// use shuftlib::common::cards::{ItalianRank, Suit};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
-    /// let player_id = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
+    /// let player_id = PlayerId::new(0, TressetteRules::PLAYERS).unwrap();
     /// let mut player = Player::<TressetteRules>::new(player_id);
     /// // Players have no cards when created.
     /// assert_eq!(player.hand().len(), 0);
@@ -64,7 +217,7 @@ where
     /// assert_eq!(player.hand().len(), 1);
     /// ```
     pub fn give(&mut self, card: G::CardType) {
-        self.hand.push(card);
+        self.hand.insert(card);
     }
 
     /// Removes a card from the hand of the player.
@@ -75,7 +228,7 @@ where
     /// use shuftlib::common::cards::{ItalianRank, Suit};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
-    /// let player_id = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
+    /// let player_id = PlayerId::new(0, TressetteRules::PLAYERS).unwrap();
     /// let mut player = Player::<TressetteRules>::new(player_id);
     /// // Players have no cards when created.
     /// assert_eq!(player.hand().len(), 0);
@@ -88,16 +241,16 @@ where
     /// assert_eq!(player.hand().len(), 0);
     /// ```
     pub fn remove(&mut self, card: G::CardType) {
-        self.hand.retain(|&c| c != card);
+        self.hand.remove(card);
     }
 
     /// Getter for the cards held by this player.
-    pub fn hand(&self) -> &[G::CardType] {
-        &self.hand
+    pub fn hand(&self) -> CardSet<G::CardType> {
+        self.hand
     }
 
     /// Getter for the id of this player.
-    pub fn id(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn id(&self) -> PlayerId {
         self.id
     }
 
@@ -109,26 +262,31 @@ where
     /// ```
     /// use shuftlib::{common::hands::{Player, PlayerId, TrickTakingGame}, tressette::TressetteRules};
     ///
-    /// let id = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
+    /// let id = PlayerId::new(0, TressetteRules::PLAYERS).unwrap();
     /// let player = Player::<TressetteRules>::new(id);
     ///
     /// assert_eq!(*player.id(), 0);
     /// assert_eq!(player.hand().len(), 0);
     /// ````
-    pub fn new(id: PlayerId<{ G::PLAYERS }>) -> Self {
+    pub fn new(id: PlayerId) -> Self {
         Self {
             id,
-            hand: Vec::new(),
+            hand: CardSet::new(),
         }
     }
 }
 
-/// A player id can only be in the range 0..N, where N depends on the game being
-/// played and it's the number of players playing that specific game.
-#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
-pub struct PlayerId<const PLAYERS: usize>(usize);
+/// A player id can only be in the range 0..N, where N is the number of players
+/// sitting at the table. Every id carries N along, so that turns can be
+/// advanced without knowing which game is being played.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PlayerId {
+    id: usize,
+    players: usize,
+}
 
-impl<const PLAYERS: usize> PlayerId<PLAYERS> {
+impl PlayerId {
     /// This method simply increments `self` by 1. Note that `PlayerId` can only
     /// be in the range 0..N, so incrementing `self` when the value is N-1, will
     /// reset its value to 0, since the purpose of this type is to determine the
@@ -137,118 +295,288 @@ impl<const PLAYERS: usize> PlayerId<PLAYERS> {
     ///
     /// # Examples
     /// ```
-    /// #![feature(generic_const_exprs)]
     /// use shuftlib::common::hands::PlayerId;
     ///
-    /// let mut player_id: PlayerId<4>= PlayerId::new(0).unwrap();
+    /// let mut player_id = PlayerId::new(0, 4).unwrap();
     /// player_id.inc();
-    /// assert_eq!(player_id, PlayerId::<4>::new(1).unwrap());
+    /// assert_eq!(player_id, PlayerId::new(1, 4).unwrap());
     /// player_id.inc();
     /// player_id.inc();
     /// player_id.inc();
-    /// assert_eq!(player_id, PlayerId::<4>::new(0).unwrap());
+    /// assert_eq!(player_id, PlayerId::new(0, 4).unwrap());
     /// ```
     pub fn inc(&mut self) {
-        if self.0 < PLAYERS - 1 {
-            self.0 += 1;
+        if self.id < self.players - 1 {
+            self.id += 1;
+        } else {
+            self.id = 0;
+        }
+    }
+
+    /// The opposite of `inc`: decrements `self` by 1, going back to N-1 when
+    /// the value is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::hands::PlayerId;
+    ///
+    /// let mut player_id = PlayerId::new(0, 4).unwrap();
+    /// player_id.dec();
+    /// assert_eq!(player_id, PlayerId::new(3, 4).unwrap());
+    /// ```
+    pub fn dec(&mut self) {
+        if self.id > 0 {
+            self.id -= 1;
         } else {
-            self.0 = 0;
+            self.id = self.players - 1;
         }
     }
 
-    /// Creates a value of type `PlayerId`. Returns None if value is >= N,
-    /// otherwise returns Some(PlayerId(value)).
+    /// Creates the id of a player sitting at a table of `players` players.
+    /// Returns None if `value` is not in the range 0..`players`.
     ///
     /// # Examples
     ///
     /// ```
     /// use shuftlib::common::hands::PlayerId;
     ///
-    /// let id = PlayerId::<4>::new(0);
+    /// let id = PlayerId::new(0, 4);
     /// assert!(id.is_some());
     ///
-    /// let id = PlayerId::<4>::new(4);
+    /// let id = PlayerId::new(4, 4);
     /// assert!(id.is_none());
     /// ```
-    pub fn new(value: usize) -> Option<Self> {
-        if value < PLAYERS {
-            Some(PlayerId(value))
+    pub fn new(value: usize, players: usize) -> Option<Self> {
+        if value < players {
+            Some(PlayerId { id: value, players })
         } else {
             None
         }
     }
-}
 
-impl<const PLAYERS: usize> TryFrom<usize> for PlayerId<PLAYERS> {
-    type Error = anyhow::Error;
+    /// Returns the number of players sitting at the table this id belongs to.
+    pub fn players(&self) -> usize {
+        self.players
+    }
+}
 
-    fn try_from(value: usize) -> Result<Self, Self::Error> {
-        if (0..PLAYERS).contains(&value) {
-            Ok(PlayerId(value))
-        } else {
-            bail!(
-                "Tried to convert {} into a PlayerId, but acceptable values are in range 0..PLAYERS",
-                value
-            )
-        }
+impl From<PlayerId> for usize {
+    fn from(value: PlayerId) -> Self {
+        value.id
     }
 }
 
-impl<const PLAYERS: usize> Display for PlayerId<PLAYERS> {
+impl Display for PlayerId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.id)
     }
 }
 
-impl<const PLAYERS: usize> Deref for PlayerId<PLAYERS> {
+impl Deref for PlayerId {
     type Target = usize;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.id
     }
 }
 
-/// A trick is a set containing the cards played and the player who won the
-/// trick, represented as `PlayerId`.
-#[derive(Debug, Copy, Clone)]
+/// A trick is a set containing the cards played, the player who played first
+/// and the player who won the trick, represented as `PlayerId`. Tricks
+/// written before the player who played first was recorded don't have one.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Trick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
-    cards: [G::CardType; G::PLAYERS],
-    taker: PlayerId<{ G::PLAYERS }>,
+    cards: Vec<G::CardType>,
+    first_to_play: Option<PlayerId>,
+    taker: PlayerId,
 }
 
+/// Writes the trick in the notation parsed by `FromStr`. See
+/// `common::render` to show it to players.
 impl<G> Display for Trick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} {} {} {} {}",
-            self.cards[0], self.cards[1], self.cards[2], self.cards[3], self.taker
-        )
+        for card in &self.cards {
+            write!(f, "{} ", card)?;
+        }
+        if let Some(first_to_play) = self.first_to_play {
+            write!(f, "{} ", first_to_play)?;
+        }
+        write!(f, "{}", self.taker)
+    }
+}
+
+impl<G> FromStr for Trick<G>
+where
+    G: TrickTakingGame,
+    G::CardType: FromStr<Err = ParseCardError>,
+{
+    type Err = ParseTrickError;
+
+    /// Parses a trick in the same notation used by `Display`: the cards
+    /// played, ordered by `PlayerId`, followed by the id of the player who
+    /// played first and by the id of the taker. The player who played first
+    /// can be left out, as tricks were written before it was recorded. The
+    /// number of players is the number of cards found, and the taker isn't
+    /// checked against the rules of the game. Use `Trick::parse` to check the
+    /// number of cards against the table the trick was played at.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::hands::{PlayerId, Trick};
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// let trick: Trick<TressetteRules> = "1H 2H 3H 4H 0 2".parse().unwrap();
+    /// assert_eq!(trick.first_to_play(), PlayerId::new(0, 4));
+    /// assert_eq!(trick.taker(), PlayerId::new(2, 4).unwrap());
+    /// assert_eq!(trick.to_string(), "1H 2H 3H 4H 0 2");
+    ///
+    /// let trick: Trick<TressetteRules> = "1H 2H 1 0".parse().unwrap();
+    /// assert_eq!(trick.to_string(), "1H 2H 1 0");
+    ///
+    /// let trick: Trick<TressetteRules> = "1H 2H 3H 4H 2".parse().unwrap();
+    /// assert_eq!(trick.first_to_play(), None);
+    /// assert_eq!(trick.to_string(), "1H 2H 3H 4H 2");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, taker) = s
+            .trim_end()
+            .rsplit_once(char::is_whitespace)
+            .ok_or(ParseTrickError::MissingTaker)?;
+        // Cards are never written with digits only, so a number before the
+        // taker is the id of the player who played first.
+        let (cards, first_to_play) = match rest.trim_end().rsplit_once(char::is_whitespace) {
+            Some((cards, id)) if id.chars().all(|c| c.is_ascii_digit()) => (cards, Some(id)),
+            _ => (rest, None),
+        };
+
+        let cards = parse_cards::<G::CardType>(cards)?;
+        let player = |id: &str| {
+            id.parse::<usize>()
+                .ok()
+                .and_then(|id| PlayerId::new(id, cards.len()))
+        };
+        let first_to_play = first_to_play
+            .map(|id| player(id).ok_or_else(|| ParseTrickError::InvalidFirstToPlay(id.to_string())))
+            .transpose()?;
+        let taker =
+            player(taker).ok_or_else(|| ParseTrickError::InvalidTaker(taker.to_string()))?;
+
+        Ok(Trick {
+            cards,
+            first_to_play,
+            taker,
+        })
     }
 }
 
+/// The reasons why parsing a `Trick` might fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTrickError {
+    /// One of the cards couldn't be parsed.
+    Card(ParseCardsError),
+    /// The amount of cards doesn't match the number of players.
+    WrongLength {
+        /// The number of players of the table.
+        expected: usize,
+        /// The amount of cards found.
+        found: usize,
+    },
+    /// The id of the taker is missing.
+    MissingTaker,
+    /// The id of the taker isn't the id of any of the players of the trick.
+    InvalidTaker(String),
+    /// The id of the player who played first isn't the id of any of the
+    /// players of the trick.
+    InvalidFirstToPlay(String),
+}
+
+impl From<ParseCardsError> for ParseTrickError {
+    fn from(value: ParseCardsError) -> Self {
+        ParseTrickError::Card(value)
+    }
+}
+
+impl Display for ParseTrickError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseTrickError::Card(e) => write!(f, "{}", e),
+            ParseTrickError::WrongLength { expected, found } => write!(
+                f,
+                "a trick needs {} cards, but {} were found",
+                expected, found
+            ),
+            ParseTrickError::MissingTaker => write!(f, "the taker of the trick is missing"),
+            ParseTrickError::InvalidTaker(s) => write!(f, "\"{}\" is not a valid taker", s),
+            ParseTrickError::InvalidFirstToPlay(s) => {
+                write!(f, "\"{}\" is not a valid player to play first", s)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseTrickError {}
+
 impl<G> Trick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
+    /// Parses a trick played at the table described by `game`, in the
+    /// notation used by `FromStr`, checking that there's a card for each of
+    /// its players.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the trick can't be parsed or if the number of cards doesn't
+    /// match the number of players of `game`.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::hands::{ParseTrickError, Trick};
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// let rules = TressetteRules::default();
+    /// assert!(Trick::parse(&rules, "1H 2H 3H 4H 0 2").is_ok());
+    /// assert_eq!(
+    ///     Trick::parse(&rules, "1H 2H 1 0"),
+    ///     Err(ParseTrickError::WrongLength { expected: 4, found: 2 })
+    /// );
+    /// ```
+    pub fn parse(game: &G, s: &str) -> Result<Self, ParseTrickError>
+    where
+        G::CardType: FromStr<Err = ParseCardError>,
+    {
+        let trick: Self = s.parse()?;
+        if trick.cards.len() != game.players() {
+            return Err(ParseTrickError::WrongLength {
+                expected: game.players(),
+                found: trick.cards.len(),
+            });
+        }
+
+        Ok(trick)
+    }
+
     /// Returns the card this trick has been won with.
     pub fn taken_with(&self) -> G::CardType {
-        self.cards[self.taker.0]
+        self.cards[*self.taker]
     }
 
     /// Getter for the `PlayerId` of the player who won the trick.
-    pub fn taker(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn taker(&self) -> PlayerId {
         self.taker
     }
 
+    /// Getter for the `PlayerId` of the player who played first. It's None
+    /// for tricks written before it was recorded.
+    pub fn first_to_play(&self) -> Option<PlayerId> {
+        self.first_to_play
+    }
+
     /// Getter for the cards played during this trick.
     pub fn cards(&self) -> &[G::CardType] {
         &self.cards
@@ -257,24 +585,23 @@ where
 
 /// A temporary state of a trick that's still not over: not all the players made
 /// their move or a taker hasn't been determined yet.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct OngoingTrick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
-    cards: [Option<G::CardType>; G::PLAYERS],
-    first_to_play: PlayerId<{ G::PLAYERS }>,
-    next_to_play: PlayerId<{ G::PLAYERS }>,
+    cards: Vec<Option<G::CardType>>,
+    first_to_play: PlayerId,
+    next_to_play: PlayerId,
     play_count: usize,
+    context: HandContext,
 }
 
 impl<G> Deref for OngoingTrick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
-    type Target = [Option<G::CardType>; G::PLAYERS];
+    type Target = [Option<G::CardType>];
 
     fn deref(&self) -> &Self::Target {
         &self.cards
@@ -284,32 +611,50 @@ where
 impl<G> OngoingTrick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     /// Adds the `Card` passed as parameter to the `OngoingTrick`.
     /// Checking the validity of the card played is a responsability of the
-    /// caller.
+    /// caller, see `TrickTakingGame::play` for a checked alternative.
+    ///
+    /// # Errors
+    ///
+    /// Fails if every player already played their card.
     ///
     /// # Examples
     /// ```
-    /// #![feature(generic_const_exprs)]
     /// use shuftlib::common::{hands::{OngoingTrick, PlayerId, TrickTakingGame}, cards::{Card, ItalianRank, Suit}};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
-    /// let first_to_play = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
+    /// let rules = TressetteRules::default();
+    /// let first_to_play = rules.player(0).unwrap();
     /// let card = TressetteCard::new(ItalianRank::Ace, Suit::Hearts);
-    /// let mut trick = OngoingTrick::<TressetteRules>::new(first_to_play);
-    /// trick.play(card);
+    /// let mut trick = OngoingTrick::new(&rules, first_to_play).unwrap();
+    /// trick.play(card).unwrap();
     /// let mut second_to_play = first_to_play;
     /// second_to_play.inc();
     ///
     /// assert_eq!(trick[0], Some(card));
     /// assert_eq!(trick.next_to_play(), second_to_play)
     /// ```
-    pub fn play(&mut self, card: G::CardType) {
-        self.cards[self.next_to_play.0] = Some(card);
+    pub fn play(&mut self, card: G::CardType) -> Result<(), PlayError> {
+        if self.is_complete() {
+            return Err(PlayError::TrickComplete);
+        }
+
+        self.cards[*self.next_to_play] = Some(card);
         self.next_to_play.inc();
         self.play_count += 1;
+        Ok(())
+    }
+
+    /// Returns true if every player already played their card.
+    pub fn is_complete(&self) -> bool {
+        self.play_count == self.cards.len()
+    }
+
+    /// Returns the card played by the first player of the trick, if any.
+    pub fn lead(&self) -> Option<G::CardType> {
+        self.cards[*self.first_to_play]
     }
 
     /// Tries to transform the current `OngoingTrick` into a `Trick` by
@@ -326,7 +671,6 @@ where
     /// # Examples
     ///
     /// ```
-    /// #![feature(generic_const_exprs)]
     /// use shuftlib::common::{hands::{OngoingTrick, PlayerId, TrickTakingGame}, cards::{ItalianRank, Suit}};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
@@ -336,46 +680,35 @@ where
     ///   TressetteCard::new(ItalianRank::Three, Suit::Hearts),
     ///   TressetteCard::new(ItalianRank::Four, Suit::Hearts),
     /// ];
-    /// let first_to_play = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
-    /// let mut ongoing_trick = OngoingTrick::<TressetteRules>::new(first_to_play);
-    /// ongoing_trick.play(cards[0]);
+    /// let rules = TressetteRules::default();
+    /// let first_to_play = rules.player(0).unwrap();
+    /// let mut ongoing_trick = OngoingTrick::new(&rules, first_to_play).unwrap();
+    /// ongoing_trick.play(cards[0]).unwrap();
     ///
     /// // After only playing a card, it's not possible to finish the OngoingTrick.
-    /// assert!(ongoing_trick.clone().finish().is_none());
+    /// assert!(ongoing_trick.clone().finish(&rules).is_none());
     ///
     /// let mut to_play = first_to_play;
     /// to_play.inc();
     /// cards.iter().skip(1).for_each(|&c| {
-    ///   ongoing_trick.play(c);
+    ///   ongoing_trick.play(c).unwrap();
     ///   to_play.inc();
     /// });
     ///
     /// // After every player made their play, it's possible to get the trick.
-    /// let trick = ongoing_trick.finish().unwrap();
+    /// let trick = ongoing_trick.finish(&rules).unwrap();
     /// // Finishing the trick also means determining a taker. Since in this
     /// // example we are using the tressette game rules, player 2 is the taker.
-    /// assert_eq!(Some(trick.taker()), PlayerId::<{TressetteRules::PLAYERS}>::new(2));
-    /// ```
-    pub fn finish(self) -> Option<Trick<G>> {
-        let mut cards: [G::CardType; G::PLAYERS] = [G::CardType::default(); G::PLAYERS];
-        if self
-            .iter()
-            .enumerate()
-            .map(|(i, &x)| {
-                if let Some(c) = x {
-                    cards[i] = c;
-                    true
-                } else {
-                    false
-                }
-            })
-            .any(|is_some| !is_some)
-        {
-            return None;
-        }
-
-        let taker = G::determine_taker(&cards, self.first_to_play);
-        Some(Trick { cards, taker })
+    /// assert_eq!(Some(trick.taker()), rules.player(2));
+    /// ```
+    pub fn finish(self, game: &G) -> Option<Trick<G>> {
+        let cards = self.cards.iter().copied().collect::<Option<Vec<_>>>()?;
+        let taker = game.determine_taker(&cards, self.first_to_play, &self.context);
+        Some(Trick {
+            cards,
+            first_to_play: Some(self.first_to_play),
+            taker,
+        })
     }
 
     /// Getter for the cards contained in this `OngoingTrick`.
@@ -384,17 +717,30 @@ where
     }
 
     /// Getter for the id of the person who starts the trick.
-    pub fn first_to_play(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn first_to_play(&self) -> PlayerId {
         self.first_to_play
     }
 
     /// Getter for the id of the person who playes last in the trick.
-    pub fn next_to_play(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn next_to_play(&self) -> PlayerId {
         self.next_to_play
     }
 
-    /// Creates a new `OngoingTrick`, by defining the logic to determine the
-    /// taker.
+    /// Returns the context of the hand this trick belongs to.
+    pub fn context(&self) -> &HandContext {
+        &self.context
+    }
+
+    /// Sets the context of the hand this trick belongs to. Tricks created with
+    /// `new` have an empty context, without trump.
+    pub fn with_context(mut self, context: HandContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Creates a new `OngoingTrick`, with room for a card for each of the
+    /// players of `game`. Returns None if `first_to_play` doesn't sit at a
+    /// table with as many players as `game`.
     ///
     /// # Examples.
     ///
@@ -402,20 +748,32 @@ where
     /// use shuftlib::common::hands::{OngoingTrick, PlayerId, TrickTakingGame};
     /// use shuftlib::tressette::TressetteRules;
     ///
-    /// let first_to_play = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
-    /// let ongoing_trick = OngoingTrick::<TressetteRules>::new(first_to_play);
+    /// let rules = TressetteRules::default();
+    /// let first_to_play = rules.player(0).unwrap();
+    /// let ongoing_trick = OngoingTrick::new(&rules, first_to_play).unwrap();
     ///
     /// assert_eq!(ongoing_trick.first_to_play(), first_to_play);
     /// ongoing_trick.cards().iter().for_each(|&c| assert!(c.is_none()));
+    ///
+    /// // Tressette is played by 4 players, not 2.
+    /// let stranger = PlayerId::new(0, 2).unwrap();
+    /// assert!(OngoingTrick::new(&rules, stranger).is_none());
     /// ```
-    pub fn new(first_to_play: PlayerId<{ G::PLAYERS }>) -> Self {
-        let mut last_to_play = first_to_play;
-        (0..G::PLAYERS - 1).for_each(|_| last_to_play.inc());
+    pub fn new(game: &G, first_to_play: PlayerId) -> Option<Self> {
+        if first_to_play.players() != game.players() {
+            return None;
+        }
+
+        Some(Self::empty(first_to_play))
+    }
+
+    fn empty(first_to_play: PlayerId) -> Self {
         Self {
-            cards: [None; G::PLAYERS],
+            cards: vec![None; first_to_play.players()],
             first_to_play,
             next_to_play: first_to_play,
             play_count: 0,
+            context: HandContext::default(),
         }
     }
 }
@@ -423,49 +781,50 @@ where
 /// Various games are usually played multiple times, until one team reaches a
 /// certain score. These "multiple times" are called hands: "We played a game of
 /// tressette and our team won in just 2 hands!". This type is generic over the
-/// actual card type, the number of players allowed and the number of tricks it
-/// takes to finish the hand.
-#[derive(Debug, Clone, Copy)]
+/// rules of the game, which define the card type, the number of players and the
+/// number of tricks it takes to finish the hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Hand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
-    tricks: [Trick<G>; G::TRICKS],
+    tricks: Vec<Trick<G>>,
+    context: HandContext,
 }
 
 impl<G> Hand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
     /// Returns a reference to the tricks of this [`Hand<G>`].
-    pub fn tricks(&self) -> &[Trick<G>; G::TRICKS] {
+    pub fn tricks(&self) -> &[Trick<G>] {
         &self.tricks
     }
+
+    /// Returns the context the hand has been played with.
+    pub fn context(&self) -> &HandContext {
+        &self.context
+    }
 }
 
 /// A hand takes multiple turns for each player to be completed, this is the
 /// representation of a `Hand` which hasn't been completed yet.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct OngoingHand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
-    current_trick: Option<OngoingTrick<G>>,
-    index: usize,
-    tricks: [Option<Trick<G>>; G::TRICKS],
+    // The fields are updated by `Match`, as the hand is played.
+    pub(super) current_trick: Option<OngoingTrick<G>>,
+    pub(super) index: usize,
+    pub(super) tricks: Vec<Option<Trick<G>>>,
+    pub(super) context: HandContext,
+    pub(super) auction: Option<Auction<G::CardType>>,
 }
 
 impl<G> OngoingHand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
     /// Returns the current trick of this [`OngoingHand<G>`].
     pub fn current_trick(&self) -> &Option<OngoingTrick<G>> {
@@ -473,7 +832,7 @@ where
     }
 
     /// Returns a reference to the tricks of this [`OngoingHand<G>`].
-    pub fn tricks(&self) -> &[Option<Trick<G>>; G::TRICKS] {
+    pub fn tricks(&self) -> &[Option<Trick<G>>] {
         &self.tricks
     }
 
@@ -482,47 +841,61 @@ where
         self.index
     }
 
+    /// Returns the context this hand is played with.
+    pub fn context(&self) -> &HandContext {
+        &self.context
+    }
+
+    /// Sets the context this hand is played with. Hands created with `new`
+    /// have an empty context, without trump.
+    pub fn with_context(mut self, context: HandContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Returns the auction opening this hand, if the game has one.
+    pub fn auction(&self) -> Option<&Auction<G::CardType>> {
+        self.auction.as_ref()
+    }
+
+    /// Opens this hand with an auction. Hands created with `new` have none.
+    pub fn with_auction(mut self, auction: Auction<G::CardType>) -> Self {
+        self.auction = Some(auction);
+        self
+    }
+
     /// Transforms an `OngoingHand` into a `Hand`, a read-only data structure
     /// used to just story the information related to a hand that has been played.
     pub fn finish(self) -> Option<Hand<G>> {
-        if self.tricks.iter().any(|t| t.is_none()) {
-            return None;
-        }
-
-        let tricks: [Trick<G>; G::TRICKS] = self
-            .tricks
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .try_into()
-            .ok()?;
-        Some(Hand { tricks })
+        let tricks = self.tricks.into_iter().collect::<Option<Vec<_>>>()?;
+        Some(Hand {
+            tricks,
+            context: self.context,
+        })
     }
 
-    /// Constructor for `OngoingHand`. All the internal fields are initialized
-    /// as empty or None.
+    /// Constructor for `OngoingHand`, with room for the tricks of `game`. All
+    /// the internal fields are initialized as empty or None.
     ///
     /// # Examples
     ///
     /// ```
     /// use shuftlib::{common::{hands::OngoingHand}, tressette::TressetteRules};
     ///
-    /// let ongoing_hand = OngoingHand::<TressetteRules>::new();
+    /// let ongoing_hand = OngoingHand::new(&TressetteRules::default());
     ///
     /// assert_eq!(ongoing_hand.index(), 0);
+    /// assert_eq!(ongoing_hand.tricks().len(), 10);
     /// assert!(ongoing_hand.current_trick().is_none());
     /// ongoing_hand.tricks().iter().for_each(|t| assert!(t.is_none()));
     /// ```
-    pub fn new() -> Self {
-        let tricks: [Option<Trick<G>>; G::TRICKS] = array_init::array_init(|_| None);
-
-        let current_trick = None;
-        let index = 0;
-
+    pub fn new(game: &G) -> Self {
         Self {
-            tricks,
-            current_trick,
-            index,
+            tricks: (0..game.tricks()).map(|_| None).collect(),
+            current_trick: None,
+            index: 0,
+            context: HandContext::default(),
+            auction: None,
         }
     }
 
@@ -534,23 +907,891 @@ where
 
 impl<G> Default for OngoingHand<G>
 where
-    G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
+    G: TrickTakingGame + Default,
 {
     fn default() -> Self {
-        Self::new()
+        Self::new(&G::default())
+    }
+}
+
+/// What is known about a hand before its first trick is played, on top of the
+/// cards dealt: the trump suit, if any, and for games where players bid for
+/// the right to choose how the hand is played, who won the bidding and how
+/// much they bid. Every trick of the hand carries it, so that the rules can
+/// consult it when determining the taker or checking the cards played.
+///
+/// The partner of the declarer might only be known while the hand is played,
+/// as in briscola chiamata, and it's added to the context once it is.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{cards::Suit, hands::{HandContext, PlayerId, Side}};
+///
+/// let declarer = PlayerId::new(2, 5).unwrap();
+/// let context = HandContext::default()
+///     .with_trump(Suit::Spades)
+///     .with_declarer(declarer)
+///     .with_contract(71);
+///
+/// assert_eq!(context.trump(), Some(Suit::Spades));
+/// assert_eq!(context.declarer(), Some(declarer));
+/// assert_eq!(HandContext::default().trump(), None);
+///
+/// let partner = PlayerId::new(4, 5).unwrap();
+/// let defender = PlayerId::new(0, 5).unwrap();
+/// assert_eq!(context.side_of(defender), None);
+/// let context = context.with_partner(partner);
+/// assert_eq!(context.side_of(partner), Some(Side::Declaring));
+/// assert_eq!(context.side_of(defender), Some(Side::Defending));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandContext {
+    trump: Option<Suit>,
+    declarer: Option<PlayerId>,
+    contract: Option<u32>,
+    partner: Option<PlayerId>,
+}
+
+impl HandContext {
+    /// Sets the trump suit, whose cards beat the ones of every other suit.
+    pub fn with_trump(mut self, trump: Suit) -> Self {
+        self.trump = Some(trump);
+        self
+    }
+
+    /// Sets the player who won the bidding.
+    pub fn with_declarer(mut self, declarer: PlayerId) -> Self {
+        self.declarer = Some(declarer);
+        self
+    }
+
+    /// Sets the amount the declarer has bid to make, counted in the unit of
+    /// the game being played, such as points or tricks.
+    pub fn with_contract(mut self, contract: u32) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    /// Sets the partner of the declarer, once it's known. A declarer playing
+    /// alone is their own partner.
+    pub fn with_partner(mut self, partner: PlayerId) -> Self {
+        self.partner = Some(partner);
+        self
+    }
+
+    /// Returns the trump suit, if the hand has one.
+    pub fn trump(&self) -> Option<Suit> {
+        self.trump
+    }
+
+    /// Returns the player who won the bidding, if any.
+    pub fn declarer(&self) -> Option<PlayerId> {
+        self.declarer
+    }
+
+    /// Returns the amount the declarer has bid to make, if any.
+    pub fn contract(&self) -> Option<u32> {
+        self.contract
+    }
+
+    /// Returns the partner of the declarer, if it's known. It's the declarer
+    /// themselves if they play alone.
+    pub fn partner(&self) -> Option<PlayerId> {
+        self.partner
+    }
+
+    /// Returns the side `player` is on: the declarer and their partner play
+    /// against everyone else. It's None if there's no declarer, and for
+    /// everyone but the declarer until their partner is known.
+    pub fn side_of(&self, player: PlayerId) -> Option<Side> {
+        let declarer = self.declarer?;
+        if player == declarer || Some(player) == self.partner {
+            Some(Side::Declaring)
+        } else {
+            self.partner.map(|_| Side::Defending)
+        }
+    }
+}
+
+/// The side a player is on in a hand won by a declarer, see
+/// `HandContext::side_of`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    /// The declarer, and their partner.
+    Declaring,
+    /// Everyone playing against the declarer.
+    Defending,
+}
+
+/// Identifies one of the teams playing a match. Teams are numbered from 0,
+/// see `Teams`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct TeamId(usize);
+
+impl From<usize> for TeamId {
+    fn from(value: usize) -> Self {
+        TeamId(value)
+    }
+}
+
+impl From<TeamId> for usize {
+    fn from(value: TeamId) -> Self {
+        value.0
+    }
+}
+
+impl Display for TeamId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Deref for TeamId {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Splits the players sitting at a table into teams, whose points are counted
+/// together. Players can play on their own, in partnerships fixed for the
+/// whole match, or in teams decided while a hand is being played, in which
+/// case a player isn't in any team until it's known which one they play for.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::hands::{PlayerId, Teams};
+///
+/// let player = |id| PlayerId::new(id, 5).unwrap();
+/// // Player 3 bids the most, and will play with whoever holds the card they
+/// // call.
+/// let mut teams = Teams::undecided(5, 2);
+/// let callers = teams.team(0).unwrap();
+/// assert!(teams.assign(player(3), callers));
+/// assert_eq!(teams.team_of(player(3)), Some(callers));
+/// assert_eq!(teams.team_of(player(1)), None);
+///
+/// // The called card is played by player 1, the others are on their own.
+/// assert!(teams.assign(player(1), callers));
+/// teams.assign_rest(teams.team(1).unwrap());
+/// assert!(teams.is_decided());
+/// assert_eq!(teams.members(callers), vec![player(1), player(3)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Teams {
+    count: usize,
+    members: Vec<Option<TeamId>>,
+}
+
+impl Teams {
+    /// Every player of a table of `players` plays on their own, so player N is
+    /// the only member of team N.
+    pub fn individual(players: usize) -> Self {
+        Self {
+            count: players,
+            members: (0..players).map(|id| Some(TeamId(id))).collect(),
+        }
+    }
+
+    /// Fixed partnerships, with partners sitting in alternate seats: player N
+    /// is in team N % `teams`. With 4 players and 2 teams, players 0 and 2
+    /// play against players 1 and 3. Returns None if `teams` is 0 or more
+    /// than `players`.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::hands::{PlayerId, Teams};
+    ///
+    /// let teams = Teams::partnerships(4, 2).unwrap();
+    /// let player = |id| PlayerId::new(id, 4).unwrap();
+    ///
+    /// assert!(teams.are_partners(player(0), player(2)));
+    /// assert!(!teams.are_partners(player(0), player(1)));
+    /// assert!(Teams::partnerships(4, 0).is_none());
+    /// ```
+    pub fn partnerships(players: usize, teams: usize) -> Option<Self> {
+        if teams == 0 || teams > players {
+            return None;
+        }
+
+        Some(Self {
+            count: teams,
+            members: (0..players).map(|id| Some(TeamId(id % teams))).collect(),
+        })
+    }
+
+    /// Fixed teams, where `teams[N]` is the team of player N. Teams are
+    /// numbered from 0, so it returns None if a team between 0 and the highest
+    /// one in `teams` has no players.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::hands::{PlayerId, Teams};
+    ///
+    /// let teams = Teams::fixed(&[0, 0, 1, 1, 2, 2]).unwrap();
+    /// assert_eq!(teams.count(), 3);
+    /// assert_eq!(*teams.team_of(PlayerId::new(3, 6).unwrap()).unwrap(), 1);
+    ///
+    /// assert!(Teams::fixed(&[0, 2]).is_none());
+    /// ```
+    pub fn fixed(teams: &[usize]) -> Option<Self> {
+        let count = teams.iter().max().map_or(0, |&max| max + 1);
+        if !(0..count).all(|team| teams.contains(&team)) {
+            return None;
+        }
+
+        Some(Self {
+            count,
+            members: teams.iter().map(|&team| Some(TeamId(team))).collect(),
+        })
+    }
+
+    /// `teams` teams whose members will be decided while the hand is played,
+    /// see `assign`. Until then, no player of the table is in any team.
+    pub fn undecided(players: usize, teams: usize) -> Self {
+        Self {
+            count: teams,
+            members: vec![None; players],
+        }
+    }
+
+    /// Returns the team with the given index, or None if there are not that
+    /// many teams.
+    pub fn team(&self, index: usize) -> Option<TeamId> {
+        (index < self.count).then_some(TeamId(index))
+    }
+
+    /// Returns every team, in order.
+    pub fn teams(&self) -> impl Iterator<Item = TeamId> {
+        (0..self.count).map(TeamId)
+    }
+
+    /// Returns the number of teams.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the number of players sitting at the table.
+    pub fn players(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns the team `player` plays for, or None if it's not known yet or
+    /// if they don't sit at this table.
+    pub fn team_of(&self, player: PlayerId) -> Option<TeamId> {
+        if player.players() != self.players() {
+            return None;
+        }
+
+        self.members.get(*player).copied().flatten()
+    }
+
+    /// Returns the players known to play for `team`, ordered by `PlayerId`.
+    pub fn members(&self, team: TeamId) -> Vec<PlayerId> {
+        (0..self.players())
+            .filter(|&id| self.members[id] == Some(team))
+            .filter_map(|id| PlayerId::new(id, self.players()))
+            .collect()
+    }
+
+    /// Returns true if both players are known to play for the same team.
+    pub fn are_partners(&self, a: PlayerId, b: PlayerId) -> bool {
+        match (self.team_of(a), self.team_of(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the team of every player is known.
+    pub fn is_decided(&self) -> bool {
+        self.members.iter().all(Option::is_some)
+    }
+
+    /// Puts `player` in `team`, replacing the team they were in. Returns false,
+    /// leaving the teams untouched, if `player` doesn't sit at this table or
+    /// if `team` doesn't exist.
+    pub fn assign(&mut self, player: PlayerId, team: TeamId) -> bool {
+        if player.players() != self.players() || *team >= self.count {
+            return false;
+        }
+
+        self.members[*player] = Some(team);
+        true
+    }
+
+    /// Puts every player whose team isn't known yet in `team`. It does nothing
+    /// if `team` doesn't exist.
+    pub fn assign_rest(&mut self, team: TeamId) {
+        if *team >= self.count {
+            return;
+        }
+
+        self.members
+            .iter_mut()
+            .filter(|t| t.is_none())
+            .for_each(|t| *t = Some(team));
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use serde_impls::AtTable;
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    //! Tricks are sized by the number of players sitting at the table, so
+    //! the ids they contain are validated against their number of cards when
+    //! deserializing. The number of players and tricks of the table itself is
+    //! only known to the rules of the game, so it's checked by `AtTable`.
+
+    use std::marker::PhantomData;
+
+    use serde::{
+        de::{DeserializeSeed, Error},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::{
+        Auction, Hand, HandContext, OngoingHand, OngoingTrick, PlayerId, Ranked, TeamId, Teams,
+        Trick, TrickTakingGame,
+    };
+
+    fn check_len<E: Error>(found: usize, expected: usize, what: &str) -> Result<(), E> {
+        if found == expected {
+            Ok(())
+        } else {
+            Err(E::custom(format!(
+                "expected {} {}, found {}",
+                expected, what, found
+            )))
+        }
+    }
+
+    /// Deserializes a `Trick`, an `OngoingTrick`, a `Hand` or an
+    /// `OngoingHand` played at the table described by a game, checking that
+    /// there's a card for each of its players and a trick for each of its
+    /// tricks. Their `Deserialize` impls can only check that they are
+    /// consistent with themselves.
+    ///
+    /// # Examples
+    /// ```
+    /// use serde::de::DeserializeSeed;
+    /// use shuftlib::common::hands::{AtTable, Hand, OngoingHand};
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// let json = serde_json::to_string(&OngoingHand::new(&TressetteRules::default())).unwrap();
+    ///
+    /// let four = TressetteRules::default();
+    /// let mut deserializer = serde_json::Deserializer::from_str(&json);
+    /// let hand = AtTable::<_, OngoingHand<_>>::new(&four).deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(hand.tricks().len(), 10);
+    ///
+    /// // Two players play 20 tricks each hand.
+    /// let two = TressetteRules::new(2).unwrap();
+    /// let mut deserializer = serde_json::Deserializer::from_str(&json);
+    /// let hand = AtTable::<_, OngoingHand<_>>::new(&two).deserialize(&mut deserializer);
+    /// assert!(hand.is_err());
+    /// ```
+    #[derive(Debug)]
+    pub struct AtTable<'a, G, T> {
+        game: &'a G,
+        value: PhantomData<T>,
+    }
+
+    impl<'a, G, T> AtTable<'a, G, T> {
+        /// Creates a seed deserializing values played at the table of `game`.
+        pub fn new(game: &'a G) -> Self {
+            Self {
+                game,
+                value: PhantomData,
+            }
+        }
+    }
+
+    fn check_trick<G, E>(game: &G, trick: &Trick<G>) -> Result<(), E>
+    where
+        G: TrickTakingGame,
+        E: Error,
+    {
+        check_len(trick.cards.len(), game.players(), "cards")
+    }
+
+    fn check_ongoing_trick<G, E>(game: &G, trick: &OngoingTrick<G>) -> Result<(), E>
+    where
+        G: TrickTakingGame,
+        E: Error,
+    {
+        check_len(trick.cards.len(), game.players(), "cards")
+    }
+
+    impl<'de, G> DeserializeSeed<'de> for AtTable<'_, G, Trick<G>>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        type Value = Trick<G>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            let trick = Trick::deserialize(deserializer)?;
+            check_trick(self.game, &trick)?;
+            Ok(trick)
+        }
+    }
+
+    impl<'de, G> DeserializeSeed<'de> for AtTable<'_, G, OngoingTrick<G>>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        type Value = OngoingTrick<G>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            let trick = OngoingTrick::deserialize(deserializer)?;
+            check_ongoing_trick(self.game, &trick)?;
+            Ok(trick)
+        }
+    }
+
+    impl<'de, G> DeserializeSeed<'de> for AtTable<'_, G, Hand<G>>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        type Value = Hand<G>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            let hand = Hand::deserialize(deserializer)?;
+            check_len(hand.tricks.len(), self.game.tricks(), "tricks")?;
+            hand.tricks
+                .iter()
+                .try_for_each(|t| check_trick(self.game, t))?;
+            Ok(hand)
+        }
+    }
+
+    impl<'de, G> DeserializeSeed<'de> for AtTable<'_, G, OngoingHand<G>>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+        <G::CardType as Ranked>::Rank: Deserialize<'de>,
+    {
+        type Value = OngoingHand<G>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            let hand = OngoingHand::deserialize(deserializer)?;
+            check_len(hand.tricks.len(), self.game.tricks(), "tricks")?;
+            hand.tricks
+                .iter()
+                .flatten()
+                .try_for_each(|t| check_trick(self.game, t))?;
+            hand.current_trick
+                .iter()
+                .try_for_each(|t| check_ongoing_trick(self.game, t))?;
+            Ok(hand)
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct PlayerIdRepr {
+        id: usize,
+        players: usize,
+    }
+
+    impl<'de> Deserialize<'de> for PlayerId {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = PlayerIdRepr::deserialize(deserializer)?;
+            PlayerId::new(repr.id, repr.players).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "a table of {} players has no player with id {}",
+                    repr.players, repr.id
+                ))
+            })
+        }
+    }
+
+    /// Checks that `id` belongs to a table of `players` players.
+    fn seated<E: Error>(id: PlayerId, players: usize) -> Result<PlayerId, E> {
+        check_len(id.players(), players, "players")?;
+        Ok(id)
+    }
+
+    #[derive(Serialize)]
+    struct TrickRef<'a, C> {
+        cards: &'a [C],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        first_to_play: Option<PlayerId>,
+        taker: PlayerId,
+    }
+
+    #[derive(Deserialize)]
+    struct TrickRepr<C> {
+        cards: Vec<C>,
+        #[serde(default)]
+        first_to_play: Option<PlayerId>,
+        taker: PlayerId,
+    }
+
+    impl<G> Serialize for Trick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            TrickRef {
+                cards: &self.cards,
+                first_to_play: self.first_to_play,
+                taker: self.taker,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for Trick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = TrickRepr::<G::CardType>::deserialize(deserializer)?;
+            let players = repr.cards.len();
+            Ok(Trick {
+                first_to_play: repr
+                    .first_to_play
+                    .map(|id| seated(id, players))
+                    .transpose()?,
+                taker: seated(repr.taker, players)?,
+                cards: repr.cards,
+            })
+        }
+    }
+
+    #[derive(Serialize)]
+    struct OngoingTrickRef<'a, C> {
+        cards: &'a [Option<C>],
+        first_to_play: PlayerId,
+        context: HandContext,
+    }
+
+    #[derive(Deserialize)]
+    struct OngoingTrickRepr<C> {
+        cards: Vec<Option<C>>,
+        first_to_play: PlayerId,
+        #[serde(default)]
+        context: HandContext,
+    }
+
+    impl<G> Serialize for OngoingTrick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            OngoingTrickRef {
+                cards: &self.cards,
+                first_to_play: self.first_to_play,
+                context: self.context,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for OngoingTrick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        /// The turn and the amount of cards played are not serialized, they
+        /// are computed from the cards instead. This also makes sure the cards
+        /// have been played in turn order, starting from `first_to_play`.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = OngoingTrickRepr::<G::CardType>::deserialize(deserializer)?;
+            let first_to_play = seated(repr.first_to_play, repr.cards.len())?;
+            let mut trick = OngoingTrick::empty(first_to_play).with_context(repr.context);
+
+            let mut seat = first_to_play;
+            for _ in 0..repr.cards.len() {
+                let Some(card) = repr.cards[*seat] else {
+                    break;
+                };
+                trick.play(card).map_err(D::Error::custom)?;
+                seat.inc();
+            }
+
+            if trick.cards != repr.cards {
+                return Err(D::Error::custom(
+                    "the cards of the trick haven't been played in turn order",
+                ));
+            }
+
+            Ok(trick)
+        }
+    }
+
+    #[derive(Serialize)]
+    struct HandRef<'a, T> {
+        tricks: &'a [T],
+        context: HandContext,
+    }
+
+    #[derive(Deserialize)]
+    struct HandRepr<T> {
+        tricks: Vec<T>,
+        #[serde(default)]
+        context: HandContext,
+    }
+
+    impl<G> Serialize for Hand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            HandRef {
+                tricks: &self.tricks,
+                context: self.context,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for Hand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = HandRepr::<Trick<G>>::deserialize(deserializer)?;
+            Ok(Hand {
+                tricks: repr.tricks,
+                context: repr.context,
+            })
+        }
+    }
+
+    #[derive(Serialize)]
+    struct OngoingHandRef<'a, T, O, A> {
+        current_trick: &'a Option<O>,
+        index: usize,
+        tricks: &'a [Option<T>],
+        context: HandContext,
+        auction: &'a Option<A>,
+    }
+
+    #[derive(Deserialize)]
+    struct OngoingHandRepr<T, O, A> {
+        current_trick: Option<O>,
+        index: usize,
+        tricks: Vec<Option<T>>,
+        #[serde(default)]
+        context: HandContext,
+        // Option::default doesn't need A to implement Default.
+        #[serde(default = "Option::default")]
+        auction: Option<A>,
+    }
+
+    impl<G> Serialize for OngoingHand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+        <G::CardType as Ranked>::Rank: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            OngoingHandRef {
+                current_trick: &self.current_trick,
+                index: self.index,
+                tricks: &self.tricks,
+                context: self.context,
+                auction: &self.auction,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for OngoingHand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+        <G::CardType as Ranked>::Rank: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr =
+                OngoingHandRepr::<Trick<G>, OngoingTrick<G>, Auction<G::CardType>>::deserialize(
+                    deserializer,
+                )?;
+            if repr.index > repr.tricks.len() {
+                return Err(D::Error::custom(format!(
+                    "the index of the hand must be at most {}, found {}",
+                    repr.tricks.len(),
+                    repr.index
+                )));
+            }
+
+            Ok(OngoingHand {
+                current_trick: repr.current_trick,
+                index: repr.index,
+                tricks: repr.tricks,
+                context: repr.context,
+                auction: repr.auction,
+            })
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct TeamsRepr {
+        count: usize,
+        members: Vec<Option<TeamId>>,
+    }
+
+    impl<'de> Deserialize<'de> for Teams {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = TeamsRepr::deserialize(deserializer)?;
+            if let Some(team) = repr.members.iter().flatten().find(|t| t.0 >= repr.count) {
+                return Err(D::Error::custom(format!(
+                    "there are {} teams, so there's no team {}",
+                    repr.count, team
+                )));
+            }
+
+            Ok(Teams {
+                count: repr.count,
+                members: repr.members,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)]
+    mod tests {
+        use serde::de::DeserializeSeed;
+
+        use crate::{
+            common::hands::{AtTable, Hand, OngoingHand, OngoingTrick, PlayerId, Teams, Trick},
+            tressette::{TressetteCard, TressetteRules},
+        };
+
+        #[test]
+        fn trick_round_trip() {
+            let trick: Trick<TressetteRules> = "1H 2H 3H 4H 0 2".parse().unwrap();
+            let json = serde_json::to_string(&trick).unwrap();
+            let deserialized: Trick<TressetteRules> = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(deserialized.cards(), trick.cards());
+            assert_eq!(deserialized.taker(), trick.taker());
+        }
+
+        #[test]
+        fn invalid_player_id_is_rejected() {
+            assert!(serde_json::from_str::<PlayerId>(r#"{"id":3,"players":4}"#).is_ok());
+            assert!(serde_json::from_str::<PlayerId>(r#"{"id":4,"players":4}"#).is_err());
+        }
+
+        #[test]
+        fn trick_with_wrong_length_is_rejected() {
+            let trick: Trick<TressetteRules> = "1H 2H 3H 4H 0 2".parse().unwrap();
+            let mut json = serde_json::to_value(&trick).unwrap();
+            json["cards"].as_array_mut().unwrap().pop();
+
+            assert!(serde_json::from_value::<Trick<TressetteRules>>(json).is_err());
+        }
+
+        #[test]
+        fn tricks_without_the_first_to_play_are_read() {
+            let trick: Trick<TressetteRules> = "1H 2H 3H 4H 2".parse().unwrap();
+            let json = serde_json::to_value(&trick).unwrap();
+            assert!(json.get("first_to_play").is_none());
+
+            let hand = serde_json::json!({ "tricks": vec![json; 10] });
+            let hand: Hand<TressetteRules> = serde_json::from_value(hand).unwrap();
+            assert_eq!(hand.tricks()[0], trick);
+        }
+
+        #[test]
+        fn sizes_are_checked_against_the_table() {
+            let four = TressetteRules::default();
+            let two = TressetteRules::new(2).unwrap();
+
+            let trick: Trick<TressetteRules> = "1H 2H 3H 4H 0 2".parse().unwrap();
+            let json = serde_json::to_string(&trick).unwrap();
+            let mut deserializer = serde_json::Deserializer::from_str(&json);
+            let result = AtTable::<_, Trick<_>>::new(&two).deserialize(&mut deserializer);
+            assert!(result.is_err());
+            let mut deserializer = serde_json::Deserializer::from_str(&json);
+            let result = AtTable::<_, Trick<_>>::new(&four).deserialize(&mut deserializer);
+            assert_eq!(result.unwrap(), trick);
+
+            let hand = OngoingHand::new(&two);
+            let json = serde_json::to_string(&hand).unwrap();
+            let mut deserializer = serde_json::Deserializer::from_str(&json);
+            let result = AtTable::<_, OngoingHand<_>>::new(&four).deserialize(&mut deserializer);
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn ongoing_trick_must_be_played_in_turn() {
+            let rules = TressetteRules::default();
+            let mut trick = OngoingTrick::new(&rules, PlayerId::new(3, 4).unwrap()).unwrap();
+            trick.play("1H".parse::<TressetteCard>().unwrap()).unwrap();
+            trick.play("2H".parse::<TressetteCard>().unwrap()).unwrap();
+
+            let json = serde_json::to_value(&trick).unwrap();
+            let deserialized: OngoingTrick<TressetteRules> =
+                serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(deserialized.next_to_play(), trick.next_to_play());
+            assert_eq!(deserialized.cards(), trick.cards());
+
+            // Player 1 can't have played before player 0.
+            let mut json = json;
+            json["cards"][0] = serde_json::Value::Null;
+            json["cards"][1] =
+                serde_json::to_value("2H".parse::<TressetteCard>().unwrap()).unwrap();
+            assert!(serde_json::from_value::<OngoingTrick<TressetteRules>>(json).is_err());
+        }
+
+        #[test]
+        fn teams_must_exist() {
+            let teams = Teams::partnerships(4, 2).unwrap();
+            let json = serde_json::to_value(&teams).unwrap();
+            assert_eq!(
+                serde_json::from_value::<Teams>(json.clone()).unwrap(),
+                teams
+            );
+
+            let mut json = json;
+            json["members"][1] = serde_json::Value::from(2);
+            assert!(serde_json::from_value::<Teams>(json).is_err());
+        }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use proptest::collection::hash_set;
-    use proptest::{array, prelude::*};
+    use proptest::prelude::*;
 
     use crate::common::cards::{ItalianCard, ItalianRank, Suit};
 
-    use super::{OngoingTrick, PlayerId, TrickTakingGame};
+    use super::{HandContext, OngoingTrick, PlayError, Player, PlayerId, Teams, TrickTakingGame};
 
     /// Strategy to create a random `TressetteCard`.
     fn italian_card_strategy() -> impl Strategy<Value = ItalianCard> {
@@ -577,67 +1818,175 @@ mod tests {
             .prop_map(|(rank, suit)| ItalianCard::new(rank, suit))
     }
 
-    #[derive(Clone, Copy, Debug)]
-    struct TestGame {}
+    /// A game whose taker is the player with id 0, unless trumps have been
+    /// played: then it's the first player, by id, who played one. It's played
+    /// by a variable number of players.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct TestGame {
+        players: usize,
+    }
 
     impl TrickTakingGame for TestGame {
         type CardType = ItalianCard;
 
-        const PLAYERS: usize = 4;
+        fn players(&self) -> usize {
+            self.players
+        }
 
-        const TRICKS: usize = 10;
+        fn tricks(&self) -> usize {
+            10
+        }
 
         fn determine_taker(
-            _cards: &[Self::CardType; Self::PLAYERS],
-            _first_to_play: super::PlayerId<{ Self::PLAYERS }>,
-        ) -> super::PlayerId<{ Self::PLAYERS }> {
-            PlayerId::new(0).unwrap()
+            &self,
+            cards: &[Self::CardType],
+            _first_to_play: PlayerId,
+            context: &HandContext,
+        ) -> PlayerId {
+            let trump = cards.iter().position(|c| Some(c.suit()) == context.trump());
+            self.player(trump.unwrap_or(0)).unwrap()
         }
     }
 
-    /// Strategy to create an `OngoingTrick` filled with random cards. Since
-    /// the `OngoingTrick` already contains the cards, `first_to_play` is
-    /// irrelevant. Change this function accordingly if you need those to have
-    /// a specific value.
-    fn ongoing_trick_strategy() -> impl Strategy<Value = OngoingTrick<TestGame>> {
-        hash_set(italian_card_strategy(), TestGame::PLAYERS).prop_map(|hash_set| {
-            let mut cards = [None; TestGame::PLAYERS];
-            hash_set
-                .iter()
-                .enumerate()
-                .for_each(|(i, &c)| cards[i] = Some(c));
-
-            OngoingTrick {
-                cards,
-                first_to_play: PlayerId(0),
-                next_to_play: PlayerId(0),
-                play_count: 0,
-            }
-        })
+    /// Strategy to create a `TestGame` with its `OngoingTrick` filled with
+    /// random cards. Since the `OngoingTrick` already contains the cards,
+    /// `first_to_play` is irrelevant. Change this function accordingly if you
+    /// need those to have a specific value.
+    fn ongoing_trick_strategy() -> impl Strategy<Value = (TestGame, OngoingTrick<TestGame>)> {
+        (2..=6usize)
+            .prop_flat_map(|players| hash_set(italian_card_strategy(), players))
+            .prop_map(|hash_set| {
+                let game = TestGame {
+                    players: hash_set.len(),
+                };
+                let first_to_play = game.player(0).unwrap();
+                let trick = OngoingTrick {
+                    cards: hash_set.into_iter().map(Some).collect(),
+                    first_to_play,
+                    next_to_play: first_to_play,
+                    play_count: 0,
+                    context: HandContext::default(),
+                };
+                (game, trick)
+            })
     }
 
     proptest! {
         #[test]
-        fn play_method_works(cards in array::uniform4(italian_card_strategy())) {
-            let mut trick: OngoingTrick<TestGame> = OngoingTrick::new(PlayerId::new(0).unwrap());
+        fn every_player_is_in_one_team(players in 1..=8usize, teams in 1..=8usize) {
+            prop_assume!(teams <= players);
+            let partnerships = Teams::partnerships(players, teams).unwrap();
+            let individual = Teams::individual(players);
 
-            for (index, &card) in cards.iter().enumerate() {
-                // Panicking if there are duplicates in the cards array.
-                trick.play(card);
+            for teams in [partnerships, individual] {
+                prop_assert!(teams.is_decided());
+                let members: Vec<PlayerId> = teams.teams().flat_map(|t| teams.members(t)).collect();
+                prop_assert_eq!(members.len(), players);
+                for player in members {
+                    let team = teams.team_of(player).unwrap();
+                    prop_assert!(teams.members(team).contains(&player));
+                }
+            }
+        }
+
+        #[test]
+        fn undecided_teams_are_filled_in(players in 2..=8usize, declarer: usize, partner: usize) {
+            let declarer = PlayerId::new(declarer % players, players).unwrap();
+            let partner = PlayerId::new(partner % players, players).unwrap();
+            let mut teams = Teams::undecided(players, 2);
+            let (callers, others) = (teams.team(0).unwrap(), teams.team(1).unwrap());
+
+            prop_assert!(teams.assign(declarer, callers));
+            prop_assert!(!teams.is_decided());
+            prop_assert!(!teams.are_partners(declarer, partner) || declarer == partner);
+            prop_assert!(!teams.assign(PlayerId::new(0, players + 1).unwrap(), callers));
+
+            teams.assign(partner, callers);
+            teams.assign_rest(others);
+            prop_assert!(teams.is_decided());
+            prop_assert!(teams.are_partners(declarer, partner));
+            prop_assert_eq!(
+                teams.members(others).len(),
+                players - teams.members(callers).len()
+            );
+        }
+
+        #[test]
+        fn play_method_works(cards in hash_set(italian_card_strategy(), 2..=6), first in 0..6usize) {
+            let game = TestGame { players: cards.len() };
+            let first_to_play = game.player(first % cards.len()).unwrap();
+            let mut trick = OngoingTrick::new(&game, first_to_play).unwrap();
+
+            let mut seat = first_to_play;
+            for &card in cards.iter() {
+                trick.play(card).unwrap();
                 // If the card was successfully played, it will be contained
                 // inside the `OngoingTrick` struct as `Some`.
-                assert_eq!(trick[index], Some(card));
+                prop_assert_eq!(trick[*seat], Some(card));
+                seat.inc();
             }
+            prop_assert_eq!(trick.next_to_play(), first_to_play);
+            prop_assert!(trick.is_complete());
+            prop_assert_eq!(trick.lead(), Some(*cards.iter().next().unwrap()));
+
+            // The first card can't be overwritten.
+            prop_assert_eq!(trick.play(ItalianCard::new(ItalianRank::Ace, Suit::Hearts)), Err(PlayError::TrickComplete));
+            prop_assert_eq!(trick.lead(), Some(*cards.iter().next().unwrap()));
         }
 
         #[test]
-        fn finish_method_works(ongoing_trick in ongoing_trick_strategy()) {
-            let trick = ongoing_trick.finish().unwrap();
+        fn finish_method_works((game, ongoing_trick) in ongoing_trick_strategy()) {
+            let trick = ongoing_trick.clone().finish(&game).unwrap();
 
             let cards = ongoing_trick.cards();
 
-            prop_assert_eq!(trick.taker(), PlayerId::new(0).unwrap());
+            prop_assert_eq!(trick.cards().len(), game.players());
+            prop_assert_eq!(trick.taker(), game.player(0).unwrap());
             prop_assert_eq!(trick.taken_with(), cards[0].unwrap());
         }
+
+        #[test]
+        fn tricks_are_taken_with_the_trump_of_the_hand((game, ongoing_trick) in ongoing_trick_strategy(), taker in 0..6usize) {
+            let taker = taker % game.players();
+            let trump = ongoing_trick.cards()[taker].unwrap().suit();
+            let context = HandContext::default().with_trump(trump);
+            let trick = ongoing_trick.with_context(context).finish(&game).unwrap();
+
+            let first_trump = trick.cards().iter().position(|c| c.suit() == trump).unwrap();
+            prop_assert_eq!(*trick.taker(), first_trump);
+        }
+
+        #[test]
+        fn players_of_other_tables_are_rejected(players in 2..=6usize, other in 2..=6usize) {
+            let game = TestGame { players };
+            let first_to_play = PlayerId::new(0, other).unwrap();
+
+            prop_assert_eq!(OngoingTrick::new(&game, first_to_play).is_some(), players == other);
+        }
+
+        #[test]
+        fn only_the_next_player_can_play_a_card_they_hold(cards in hash_set(italian_card_strategy(), 2..=6)) {
+            let game = TestGame { players: cards.len() };
+            let mut trick = OngoingTrick::new(&game, game.player(0).unwrap()).unwrap();
+            let mut players: Vec<Player<TestGame>> = (0..game.players())
+                .map(|i| Player::new(game.player(i).unwrap()))
+                .collect();
+            let cards: Vec<ItalianCard> = cards.into_iter().collect();
+            players.iter_mut().zip(&cards).for_each(|(p, &c)| p.give(c));
+
+            prop_assert_eq!(
+                game.play(&mut players[1], cards[1], &mut trick),
+                Err(PlayError::NotYourTurn { expected: game.player(0).unwrap() })
+            );
+            prop_assert_eq!(game.play(&mut players[0], cards[1], &mut trick), Err(PlayError::CardNotInHand));
+
+            for (player, &card) in players.iter_mut().zip(&cards) {
+                prop_assert_eq!(game.play(player, card, &mut trick), Ok(()));
+                prop_assert!(player.hand().is_empty());
+            }
+
+            players[0].give(cards[0]);
+            prop_assert_eq!(game.play(&mut players[0], cards[0], &mut trick), Err(PlayError::TrickComplete));
+        }
     }
 }
@@ -0,0 +1,965 @@
+use std::fmt::Display;
+
+use num_bigint::BigUint;
+use rand::{seq::SliceRandom, RngCore};
+
+use super::{
+    cards::{Card, Deck},
+    dealing::Dealing,
+    hands::PlayerId,
+};
+
+/// The 2048 bits safe prime defined in RFC 3526 (group 14).
+const RFC3526_PRIME: &str = "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A08798E3404DDEF9519B3CD3A431B302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7EDEE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF0598DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB9ED529077096966D670C354E4ABC9804F1746C08CA18217C32905E462E36CE3BE39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF6955817183995497CEA956AE515D2261898FA051015728E5A8AACAA68FFFFFFFFFFFFFFFF";
+
+/// The group the cards are encrypted in: the quadratic residues modulo a safe
+/// prime `p = 2q + 1`. Encrypting a card means raising it to a secret
+/// exponent, which makes the encryption commutative: the layers added by the
+/// players can be removed in any order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    p: BigUint,
+    q: BigUint,
+}
+
+impl Group {
+    /// The group based on the 2048 bits safe prime of RFC 3526. This is the
+    /// group that should be used for actual games.
+    ///
+    /// # Panics
+    ///
+    /// It can only panic in case of a bug in this crate.
+    #[allow(clippy::expect_used)]
+    pub fn rfc3526() -> Self {
+        let p = BigUint::parse_bytes(RFC3526_PRIME.as_bytes(), 16).expect(
+            "The RFC 3526 prime couldn't be parsed. This shouldn't have happened, please file a bug report.",
+        );
+        let q = &p >> 1;
+        Self { p, q }
+    }
+
+    /// Creates a group from a custom prime. Returns None if `p` is even or too
+    /// small. It's a responsability of the caller to make sure `p` is a safe
+    /// prime, that is `(p - 1) / 2` is prime too, which is why groups can't be
+    /// received from other players: a prime of their choice could make every
+    /// card easy to open.
+    pub fn new(p: BigUint) -> Option<Self> {
+        if p < BigUint::from(5u8) || !p.bit(0) {
+            return None;
+        }
+
+        let q = &p >> 1;
+        Some(Self { p, q })
+    }
+
+    /// Returns the prime the group is based on.
+    pub fn prime(&self) -> &BigUint {
+        &self.p
+    }
+
+    /// Returns whether `value` can be an encrypted card of this group, that
+    /// is a quadratic residue modulo `p`. Cards are encoded as squares and
+    /// encrypting them keeps them so: anything else has been tampered with,
+    /// for instance to follow a card through the shuffles.
+    fn contains(&self, value: &BigUint) -> bool {
+        *value > BigUint::from(0u8)
+            && *value < self.p
+            && value.modpow(&self.q, &self.p) == BigUint::from(1u8)
+    }
+
+    /// Returns whether `exponent` can be a key of this group.
+    fn is_key(&self, exponent: &BigUint) -> bool {
+        *exponent > BigUint::from(0u8) && *exponent < self.q
+    }
+
+    fn random_exponent<R>(&self, rng: &mut R) -> (BigUint, BigUint)
+    where
+        R: RngCore + ?Sized,
+    {
+        let mut bytes = vec![0; (self.q.bits() as usize).div_ceil(8) + 8];
+        let two = BigUint::from(2u8);
+        loop {
+            rng.fill_bytes(&mut bytes);
+            let exponent = BigUint::from_bytes_be(&bytes) % &self.q;
+            if exponent >= two {
+                // q is prime, so the inverse can be computed with Fermat's
+                // little theorem.
+                let inverse = exponent.modpow(&(&self.q - &two), &self.q);
+                return (exponent, inverse);
+            }
+        }
+    }
+
+    fn encode(&self, index: usize) -> BigUint {
+        let value = BigUint::from(index) + 2u8;
+        (&value * &value) % &self.p
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::rfc3526()
+    }
+}
+
+/// The reasons why an operation on a `MaskedDeck` might fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MentalPokerError {
+    /// The group is too small to encode every card of the deck.
+    GroupTooSmall,
+    /// There is no card at the requested position.
+    PositionOutOfRange,
+    /// A key was meant for a card in a different position.
+    WrongPosition,
+    /// After removing the given layers of encryption, the value doesn't
+    /// correspond to any card: some keys are missing or invalid.
+    CannotOpen,
+    /// Once opened, the deck doesn't contain every card exactly once: someone
+    /// replaced or duplicated some of the cards while dealing.
+    NotAPermutation,
+    /// The encrypted cards received don't belong to the deck: there are too
+    /// many or too few of them, or some aren't elements of the group.
+    InvalidCards,
+    /// A key received from another player isn't a valid exponent of the
+    /// group.
+    InvalidKey,
+    /// The deck can't be locked by a participant who didn't shuffle it.
+    NotShuffled,
+}
+
+impl Display for MentalPokerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MentalPokerError::GroupTooSmall => write!(f, "the group is too small for this deck"),
+            MentalPokerError::PositionOutOfRange => write!(f, "there is no card in this position"),
+            MentalPokerError::WrongPosition => {
+                write!(f, "the key belongs to a card in a different position")
+            }
+            MentalPokerError::CannotOpen => {
+                write!(f, "the card is still encrypted or the keys are invalid")
+            }
+            MentalPokerError::NotAPermutation => {
+                write!(f, "the deck is not a permutation of the original cards")
+            }
+            MentalPokerError::InvalidCards => {
+                write!(f, "the encrypted cards don't belong to this deck")
+            }
+            MentalPokerError::InvalidKey => write!(f, "the key doesn't belong to this group"),
+            MentalPokerError::NotShuffled => {
+                write!(f, "the deck can't be locked before shuffling it")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MentalPokerError {}
+
+/// A deck whose cards are hidden under one layer of encryption for each
+/// player. It's a public value: every player holds a copy of it and nobody can
+/// tell which card is in which position, until the players owning the layers
+/// of that position hand over their `CardKey`.
+///
+/// Players on different machines exchange the deck after every `shuffle` and
+/// `lock`, either with serde or as the bytes returned by `to_bytes`, which
+/// the receiver turns back into a deck with `from_bytes`. Only the encrypted
+/// cards are sent: the group and the reference deck are agreed upon before
+/// dealing, and each player provides its own copy of them when receiving a
+/// deck, see `Agreed`.
+#[derive(Debug, Clone)]
+pub struct MaskedDeck<T>
+where
+    T: Card,
+{
+    group: Group,
+    reference: Vec<T>,
+    encoded: Vec<BigUint>,
+    cards: Vec<BigUint>,
+}
+
+impl<T: Card> MaskedDeck<T> {
+    /// Encodes every card of the deck as an element of the group, without
+    /// encrypting it. The order of `deck` is public and is the reference used
+    /// to decode the cards once they are opened.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the group is too small to encode every card of the deck.
+    pub fn new(group: Group, deck: &Deck<T>) -> Result<Self, MentalPokerError> {
+        if group.q <= BigUint::from(deck.len() + 2) {
+            return Err(MentalPokerError::GroupTooSmall);
+        }
+
+        let encoded: Vec<_> = (0..deck.len()).map(|i| group.encode(i)).collect();
+        Ok(Self {
+            cards: encoded.clone(),
+            encoded,
+            reference: deck.to_vec(),
+            group,
+        })
+    }
+
+    /// Rebuilds a deck from the encrypted cards received from another player,
+    /// in the format returned by `to_bytes`. `group` and `deck` must be the
+    /// ones the deck was created with.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the group is too small to encode every card of the deck, or
+    /// if `cards` doesn't contain one element of the group for every card,
+    /// quadratic residues only.
+    ///
+    /// # Examples
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use shuftlib::common::{cards::Deck, mental_poker::{Group, MaskedDeck, Participant}};
+    ///
+    /// let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+    /// let mut deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+    /// let mut alice = Participant::new(group.clone());
+    /// alice.shuffle(&mut deck, &mut rand::thread_rng());
+    ///
+    /// // Alice sends the shuffled deck to Bob.
+    /// let bytes = deck.to_bytes();
+    /// let received = MaskedDeck::from_bytes(group, &Deck::italian(), &bytes).unwrap();
+    /// assert_eq!(received.cards(), deck.cards());
+    /// ```
+    pub fn from_bytes(
+        group: Group,
+        deck: &Deck<T>,
+        cards: &[Vec<u8>],
+    ) -> Result<Self, MentalPokerError> {
+        let mut masked = Self::new(group, deck)?;
+        if cards.len() != masked.len() {
+            return Err(MentalPokerError::InvalidCards);
+        }
+
+        masked.cards = cards
+            .iter()
+            .map(|bytes| BigUint::from_bytes_be(bytes))
+            .collect();
+        if !masked.cards.iter().all(|c| masked.group.contains(c)) {
+            return Err(MentalPokerError::InvalidCards);
+        }
+
+        Ok(masked)
+    }
+
+    /// Returns the encrypted cards, big endian, in the format accepted by
+    /// `from_bytes`.
+    pub fn to_bytes(&self) -> Vec<Vec<u8>> {
+        self.cards.iter().map(BigUint::to_bytes_be).collect()
+    }
+
+    /// Returns the encrypted cards, in the order they will be dealt.
+    pub fn cards(&self) -> &[BigUint] {
+        &self.cards
+    }
+
+    /// Returns the cards of the deck in the order they had before being
+    /// encrypted, which is used to decode them once they are opened.
+    pub fn reference(&self) -> &[T] {
+        &self.reference
+    }
+
+    /// Returns the number of cards in the deck.
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Returns whether or not the deck is empty.
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Returns the group the cards are encrypted in.
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    /// Returns the positions of the cards `dealing` gives to `player`: every
+    /// other player sends them their keys for these positions, and for these
+    /// positions only. Cards are dealt from the top of the deck, that is the
+    /// end of `cards`, so that the opened deck deals the same hands with
+    /// `Deck::deal`.
+    ///
+    /// # Examples
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use shuftlib::common::{cards::Deck, dealing::Dealing, hands::PlayerId, mental_poker::{Group, MaskedDeck}};
+    ///
+    /// let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+    /// let deck = MaskedDeck::new(group, &Deck::italian()).unwrap();
+    /// let dealer = PlayerId::new(1, 2).unwrap();
+    /// let dealing = Dealing::new(dealer, vec![3]).with_face_up(1);
+    ///
+    /// assert_eq!(deck.dealt_to(&dealing, PlayerId::new(0, 2).unwrap()), vec![39, 38, 37]);
+    /// assert_eq!(deck.dealt_to(&dealing, dealer), vec![36, 35, 34]);
+    /// assert_eq!(deck.face_up(&dealing), vec![33]);
+    /// ```
+    pub fn dealt_to(&self, dealing: &Dealing, player: PlayerId) -> Vec<usize> {
+        dealing
+            .recipients()
+            .into_iter()
+            .zip(self.positions_from_top())
+            .filter(|&(id, _)| id == player)
+            .map(|(_, position)| position)
+            .collect()
+    }
+
+    /// Returns the positions of the cards `dealing` turns face up, which
+    /// every player sends their keys for.
+    pub fn face_up(&self, dealing: &Dealing) -> Vec<usize> {
+        self.positions_from_top()
+            .skip(dealing.recipients().len())
+            .take(dealing.face_up())
+            .collect()
+    }
+
+    fn positions_from_top(&self) -> impl Iterator<Item = usize> {
+        (0..self.len()).rev()
+    }
+
+    /// Removes the layers of encryption of the card in the given position and
+    /// returns it. `keys` must contain the key of every player for that
+    /// position.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `position` is out of range, if any of the keys belong to a
+    /// different position or to a different group or if, after applying the
+    /// keys, the card is still encrypted.
+    pub fn open(&self, position: usize, keys: &[CardKey]) -> Result<T, MentalPokerError> {
+        self.open_index(position, keys).map(|i| self.reference[i])
+    }
+
+    /// Opens every card of the deck with the keys revealed by the players at
+    /// the end of the hand, one `Vec` for each player as returned by
+    /// `Participant::reveal`, and returns the cards in the order they were
+    /// dealt.
+    ///
+    /// # Errors
+    ///
+    /// Fails if some keys are missing or invalid, or if the opened deck isn't
+    /// a permutation of the original one, which means that a player cheated
+    /// while shuffling or locking the deck.
+    pub fn verify(&self, keys: &[Vec<CardKey>]) -> Result<Vec<T>, MentalPokerError> {
+        let mut seen = vec![false; self.len()];
+        (0..self.len())
+            .map(|position| {
+                let position_keys: Vec<CardKey> = keys
+                    .iter()
+                    .map(|k| k.get(position).cloned().ok_or(MentalPokerError::CannotOpen))
+                    .collect::<Result<_, _>>()?;
+                let index = self.open_index(position, &position_keys)?;
+                if std::mem::replace(&mut seen[index], true) {
+                    return Err(MentalPokerError::NotAPermutation);
+                }
+
+                Ok(self.reference[index])
+            })
+            .collect()
+    }
+
+    fn open_index(&self, position: usize, keys: &[CardKey]) -> Result<usize, MentalPokerError> {
+        let mut card = self
+            .cards
+            .get(position)
+            .ok_or(MentalPokerError::PositionOutOfRange)?
+            .clone();
+
+        for key in keys {
+            if key.position != position {
+                return Err(MentalPokerError::WrongPosition);
+            }
+            if !self.group.is_key(&key.decrypt) {
+                return Err(MentalPokerError::InvalidKey);
+            }
+            card = card.modpow(&key.decrypt, &self.group.p);
+        }
+
+        self.encoded
+            .iter()
+            .position(|c| *c == card)
+            .ok_or(MentalPokerError::CannotOpen)
+    }
+}
+
+/// The secret a player hands over to let someone else remove their layer of
+/// encryption from the card in a specific position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardKey {
+    position: usize,
+    decrypt: BigUint,
+}
+
+impl CardKey {
+    /// Rebuilds a key received from another player, from the position of its
+    /// card and the bytes returned by `to_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the key isn't an exponent of `group`.
+    ///
+    /// # Examples
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use shuftlib::common::{cards::Deck, mental_poker::{CardKey, Group, MaskedDeck, Participant}};
+    ///
+    /// let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+    /// let mut deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+    /// let mut alice = Participant::new(group.clone());
+    /// let mut rng = rand::thread_rng();
+    /// alice.shuffle(&mut deck, &mut rng);
+    /// alice.lock(&mut deck, &mut rng).unwrap();
+    ///
+    /// let key = alice.card_key(3).unwrap();
+    /// let received = CardKey::from_bytes(&group, key.position(), &key.to_bytes()).unwrap();
+    /// assert_eq!(deck.open(3, &[received]), deck.open(3, &[key]));
+    /// ```
+    pub fn from_bytes(
+        group: &Group,
+        position: usize,
+        bytes: &[u8],
+    ) -> Result<Self, MentalPokerError> {
+        let decrypt = BigUint::from_bytes_be(bytes);
+        if !group.is_key(&decrypt) {
+            return Err(MentalPokerError::InvalidKey);
+        }
+
+        Ok(Self { position, decrypt })
+    }
+
+    /// The position of the card this key can open.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the secret exponent of the key.
+    pub fn decrypt(&self) -> &BigUint {
+        &self.decrypt
+    }
+
+    /// Returns the secret exponent of the key, big endian, in the format
+    /// accepted by `from_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.decrypt.to_bytes_be()
+    }
+}
+
+/// A player taking part in a dealing without a trusted dealer. Every player
+/// in turn calls `shuffle` on the shared `MaskedDeck`, then every player in
+/// turn calls `lock`. From then on, a card is dealt to a player by having
+/// every other player send them their `CardKey` for its position. Once the
+/// hand is over, every player publishes all of its keys with `reveal`, so
+/// that anyone can check with `MaskedDeck::verify` that nobody tampered with
+/// the deck.
+///
+/// # Examples
+/// ```
+/// use num_bigint::BigUint;
+/// use shuftlib::common::{cards::Deck, mental_poker::{Group, MaskedDeck, Participant}};
+///
+/// // A small group keeps the example fast, real games should use
+/// // `Group::rfc3526`.
+/// let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+/// let mut deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+/// let mut rng = rand::thread_rng();
+///
+/// let mut alice = Participant::new(group.clone());
+/// let mut bob = Participant::new(group);
+///
+/// alice.shuffle(&mut deck, &mut rng);
+/// bob.shuffle(&mut deck, &mut rng);
+/// alice.lock(&mut deck, &mut rng).unwrap();
+/// bob.lock(&mut deck, &mut rng).unwrap();
+///
+/// // Bob deals the first card to Alice: only she can see it.
+/// let keys = [bob.card_key(0).unwrap(), alice.card_key(0).unwrap()];
+/// let card = deck.open(0, &keys).unwrap();
+/// assert!(Deck::italian().contains(&card));
+///
+/// // Without Bob's key, the card can't be opened.
+/// assert!(deck.open(0, &keys[1..]).is_err());
+///
+/// // At the end of the hand the whole deal can be verified.
+/// let dealt = deck.verify(&[alice.reveal(), bob.reveal()]).unwrap();
+/// assert_eq!(dealt[0], card);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Participant {
+    group: Group,
+    shuffle_key: Option<(BigUint, BigUint)>,
+    card_keys: Vec<BigUint>,
+}
+
+impl Participant {
+    /// Creates a new participant. Keys are generated when they are needed.
+    pub fn new(group: Group) -> Self {
+        Self {
+            group,
+            shuffle_key: None,
+            card_keys: Vec::new(),
+        }
+    }
+
+    /// Encrypts every card of the deck with the same secret key and permutes
+    /// them.
+    pub fn shuffle<T, R>(&mut self, deck: &mut MaskedDeck<T>, rng: &mut R)
+    where
+        T: Card,
+        R: RngCore + ?Sized,
+    {
+        let (encrypt, decrypt) = self.group.random_exponent(rng);
+        deck.cards
+            .iter_mut()
+            .for_each(|c| *c = c.modpow(&encrypt, &self.group.p));
+        deck.cards.shuffle(rng);
+        self.shuffle_key = Some((encrypt, decrypt));
+    }
+
+    /// Replaces the layer added by `shuffle` with a different key for each
+    /// card, so that cards can then be opened one at a time.
+    ///
+    /// # Errors
+    ///
+    /// Fails if this participant didn't shuffle the deck.
+    pub fn lock<T, R>(
+        &mut self,
+        deck: &mut MaskedDeck<T>,
+        rng: &mut R,
+    ) -> Result<(), MentalPokerError>
+    where
+        T: Card,
+        R: RngCore + ?Sized,
+    {
+        let Some((_, shuffle_decrypt)) = &self.shuffle_key else {
+            return Err(MentalPokerError::NotShuffled);
+        };
+
+        self.card_keys = deck
+            .cards
+            .iter_mut()
+            .map(|c| {
+                let (encrypt, decrypt) = self.group.random_exponent(rng);
+                let exponent = (shuffle_decrypt * encrypt) % &self.group.q;
+                *c = c.modpow(&exponent, &self.group.p);
+                decrypt
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Returns the key to remove this participant's layer of encryption from
+    /// the card in the given position. Returns None if the deck hasn't been
+    /// locked yet or the position is out of range.
+    pub fn card_key(&self, position: usize) -> Option<CardKey> {
+        self.card_keys.get(position).map(|decrypt| CardKey {
+            position,
+            decrypt: decrypt.clone(),
+        })
+    }
+
+    /// Returns the keys for every position of the deck. They must only be
+    /// published once the hand is over, to let the other players verify the
+    /// deal with `MaskedDeck::verify`.
+    pub fn reveal(&self) -> Vec<CardKey> {
+        (0..self.card_keys.len())
+            .filter_map(|position| self.card_key(position))
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use serde_impls::Agreed;
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    //! Big integers are (de)serialized as their big endian bytes, the same
+    //! format used by `to_bytes` and `from_bytes`. Values received from other
+    //! players are validated as they are deserialized. The group and the
+    //! reference deck are agreed upon before dealing and never travel with
+    //! the cards: whoever picks them could open every card.
+
+    use num_bigint::BigUint;
+    use serde::{
+        de::{DeserializeSeed, Error},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::{CardKey, Group, MaskedDeck};
+    use crate::common::cards::{Card, Deck};
+
+    impl<T: Card> Serialize for MaskedDeck<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.to_bytes().serialize(serializer)
+        }
+    }
+
+    /// Deserializes a `MaskedDeck` received from another player, encrypted in
+    /// the group and built from the deck the players agreed upon. Only the
+    /// encrypted cards are sent, see `MaskedDeck::from_bytes`.
+    ///
+    /// # Examples
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use serde::de::DeserializeSeed;
+    /// use shuftlib::common::{cards::Deck, mental_poker::{Agreed, Group, MaskedDeck}};
+    ///
+    /// let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+    /// let deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+    /// let json = serde_json::to_string(&deck).unwrap();
+    ///
+    /// let mut deserializer = serde_json::Deserializer::from_str(&json);
+    /// let received = Agreed::new(&group, &Deck::italian()).deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(received.cards(), deck.cards());
+    /// ```
+    #[derive(Debug)]
+    pub struct Agreed<'a, T>
+    where
+        T: Card,
+    {
+        group: &'a Group,
+        deck: &'a Deck<T>,
+    }
+
+    impl<'a, T: Card> Agreed<'a, T> {
+        /// Creates a seed deserializing decks encrypted in `group`, whose
+        /// cards are the ones of `deck` in its order.
+        pub fn new(group: &'a Group, deck: &'a Deck<T>) -> Self {
+            Self { group, deck }
+        }
+    }
+
+    impl<'de, T: Card> DeserializeSeed<'de> for Agreed<'_, T> {
+        type Value = MaskedDeck<T>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            let cards = Vec::<Vec<u8>>::deserialize(deserializer)?;
+            MaskedDeck::from_bytes(self.group.clone(), self.deck, &cards).map_err(D::Error::custom)
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct CardKeyRepr {
+        position: usize,
+        key: Vec<u8>,
+    }
+
+    impl Serialize for CardKey {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            CardKeyRepr {
+                position: self.position,
+                key: self.to_bytes(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CardKey {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            // Without the group at hand, the key can only be checked when
+            // it's used to open a card.
+            let repr = CardKeyRepr::deserialize(deserializer)?;
+            Ok(CardKey {
+                position: repr.position,
+                decrypt: BigUint::from_bytes_be(&repr.key),
+            })
+        }
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)]
+    mod tests {
+        use num_bigint::BigUint;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+        use serde::de::DeserializeSeed;
+
+        use crate::common::{
+            cards::Deck,
+            mental_poker::{Agreed, CardKey, Group, MaskedDeck, Participant},
+        };
+
+        #[test]
+        fn decks_and_keys_round_trip() {
+            let mut rng = ChaCha20Rng::seed_from_u64(0);
+            let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+            let mut deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+            let mut player = Participant::new(group);
+            player.shuffle(&mut deck, &mut rng);
+            player.lock(&mut deck, &mut rng).unwrap();
+
+            let json = serde_json::to_string(&deck).unwrap();
+            let mut deserializer = serde_json::Deserializer::from_str(&json);
+            let received = Agreed::new(deck.group(), &Deck::italian())
+                .deserialize(&mut deserializer)
+                .unwrap();
+            let key = serde_json::to_string(&player.card_key(5).unwrap()).unwrap();
+            let key: CardKey = serde_json::from_str(&key).unwrap();
+
+            assert_eq!(received.cards(), deck.cards());
+            assert_eq!(
+                received.open(5, &[key]),
+                deck.open(5, &player.reveal()[5..6])
+            );
+        }
+
+        #[test]
+        fn cards_outside_of_the_group_are_rejected() {
+            let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+            let deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+            let mut json = serde_json::to_value(&deck).unwrap();
+            json[0] = serde_json::json!([0]);
+
+            let result = Agreed::new(&group, &Deck::italian()).deserialize(json);
+            assert!(result.is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use num_bigint::BigUint;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use crate::common::cards::{Deck, FrenchCard};
+
+    use super::{CardKey, Group, MaskedDeck, MentalPokerError, Participant};
+
+    fn small_group() -> Group {
+        Group::new(BigUint::from(9223372036854778487u64)).unwrap()
+    }
+
+    #[test]
+    fn every_card_is_dealt_once() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut deck = MaskedDeck::new(small_group(), &Deck::french()).unwrap();
+        let mut players: Vec<_> = (0..3).map(|_| Participant::new(small_group())).collect();
+
+        players
+            .iter_mut()
+            .for_each(|p| p.shuffle(&mut deck, &mut rng));
+        players
+            .iter_mut()
+            .for_each(|p| p.lock(&mut deck, &mut rng).unwrap());
+
+        let mut opened: Vec<FrenchCard> = (0..deck.len())
+            .map(|i| {
+                // Keys are handed over in reverse order on purpose: the
+                // encryption is commutative.
+                let keys: Vec<CardKey> = players
+                    .iter()
+                    .rev()
+                    .map(|p| p.card_key(i).unwrap())
+                    .collect();
+                deck.open(i, &keys).unwrap()
+            })
+            .collect();
+
+        let mut expected = Deck::french().to_vec();
+        opened.sort_by_key(|c| (c.suit() as u8, c.rank() as u8));
+        expected.sort_by_key(|c| (c.suit() as u8, c.rank() as u8));
+        assert_eq!(opened, expected);
+    }
+
+    #[test]
+    fn keys_for_other_positions_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut deck = MaskedDeck::new(small_group(), &Deck::italian()).unwrap();
+        let mut player = Participant::new(small_group());
+        player.shuffle(&mut deck, &mut rng);
+        player.lock(&mut deck, &mut rng).unwrap();
+
+        assert_eq!(
+            deck.open(0, &[player.card_key(1).unwrap()]),
+            Err(MentalPokerError::WrongPosition)
+        );
+    }
+
+    fn deal(rng: &mut ChaCha20Rng) -> (MaskedDeck<FrenchCard>, Vec<Participant>) {
+        let mut deck = MaskedDeck::new(small_group(), &Deck::french()).unwrap();
+        let mut players: Vec<_> = (0..3).map(|_| Participant::new(small_group())).collect();
+        players.iter_mut().for_each(|p| p.shuffle(&mut deck, rng));
+        players
+            .iter_mut()
+            .for_each(|p| p.lock(&mut deck, rng).unwrap());
+        (deck, players)
+    }
+
+    #[test]
+    fn honest_deals_are_verified() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (deck, players) = deal(&mut rng);
+        let keys: Vec<_> = players.iter().map(Participant::reveal).collect();
+
+        let dealt = deck.verify(&keys).unwrap();
+        assert_eq!(dealt.len(), Deck::french().len());
+        let seventh: Vec<_> = keys.iter().map(|k| k[7].clone()).collect();
+        assert_eq!(dealt[7], deck.open(7, &seventh).unwrap());
+    }
+
+    #[test]
+    fn duplicated_cards_are_caught() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut deck = MaskedDeck::new(small_group(), &Deck::french()).unwrap();
+        let mut players: Vec<_> = (0..3).map(|_| Participant::new(small_group())).collect();
+
+        players[0].shuffle(&mut deck, &mut rng);
+        // The second player copies a card over another one while shuffling,
+        // hoping it's a good one.
+        players[1].shuffle(&mut deck, &mut rng);
+        deck.cards[3] = deck.cards[5].clone();
+        players[2].shuffle(&mut deck, &mut rng);
+        players
+            .iter_mut()
+            .for_each(|p| p.lock(&mut deck, &mut rng).unwrap());
+
+        let keys: Vec<_> = players.iter().map(Participant::reveal).collect();
+        assert_eq!(deck.verify(&keys), Err(MentalPokerError::NotAPermutation));
+    }
+
+    #[test]
+    fn swapped_cards_are_caught() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (mut deck, players) = deal(&mut rng);
+
+        // Once the deck is locked, every position has its own keys: moving
+        // cards around means they can't be opened anymore.
+        deck.cards.swap(0, 1);
+
+        let keys: Vec<_> = players.iter().map(Participant::reveal).collect();
+        assert_eq!(deck.verify(&keys), Err(MentalPokerError::CannotOpen));
+    }
+
+    #[test]
+    fn missing_keys_are_caught() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (deck, players) = deal(&mut rng);
+        let mut keys: Vec<_> = players.iter().map(Participant::reveal).collect();
+        keys[1].pop();
+
+        assert_eq!(deck.verify(&keys), Err(MentalPokerError::CannotOpen));
+    }
+
+    #[test]
+    fn small_groups_are_rejected() {
+        let group = Group::new(BigUint::from(23u8)).unwrap();
+
+        assert_eq!(
+            MaskedDeck::new(group, &Deck::italian()).err(),
+            Some(MentalPokerError::GroupTooSmall)
+        );
+    }
+
+    #[test]
+    fn decks_travel_between_peers_as_bytes() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut alice = Participant::new(small_group());
+        let mut bob = Participant::new(small_group());
+
+        // Every peer keeps its own copy of the deck and replaces it with the
+        // one it receives.
+        let mut alices = MaskedDeck::new(small_group(), &Deck::italian()).unwrap();
+        alice.shuffle(&mut alices, &mut rng);
+        let mut bobs =
+            MaskedDeck::from_bytes(small_group(), &Deck::italian(), &alices.to_bytes()).unwrap();
+        bob.shuffle(&mut bobs, &mut rng);
+        alices = MaskedDeck::from_bytes(small_group(), &Deck::italian(), &bobs.to_bytes()).unwrap();
+        alice.lock(&mut alices, &mut rng).unwrap();
+        bobs = MaskedDeck::from_bytes(small_group(), &Deck::italian(), &alices.to_bytes()).unwrap();
+        bob.lock(&mut bobs, &mut rng).unwrap();
+        alices = MaskedDeck::from_bytes(small_group(), &Deck::italian(), &bobs.to_bytes()).unwrap();
+
+        // Bob sends Alice his key for the first card.
+        let bobs_key = bob.card_key(0).unwrap();
+        let received =
+            CardKey::from_bytes(&small_group(), bobs_key.position(), &bobs_key.to_bytes()).unwrap();
+        let card = alices
+            .open(0, &[received, alice.card_key(0).unwrap()])
+            .unwrap();
+
+        let dealt = bobs.verify(&[alice.reveal(), bob.reveal()]).unwrap();
+        assert_eq!(dealt[0], card);
+    }
+
+    #[test]
+    fn received_cards_must_belong_to_the_deck() {
+        let deck = MaskedDeck::new(small_group(), &Deck::italian()).unwrap();
+        let mut bytes = deck.to_bytes();
+        bytes.pop();
+        assert_eq!(
+            MaskedDeck::from_bytes(small_group(), &Deck::italian(), &bytes).err(),
+            Some(MentalPokerError::InvalidCards)
+        );
+
+        let mut bytes = deck.to_bytes();
+        bytes[0] = small_group().prime().to_bytes_be();
+        assert_eq!(
+            MaskedDeck::from_bytes(small_group(), &Deck::italian(), &bytes).err(),
+            Some(MentalPokerError::InvalidCards)
+        );
+    }
+
+    #[test]
+    fn tagged_cards_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let group = small_group();
+        let mut deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+        let mut player = Participant::new(group.clone());
+        player.shuffle(&mut deck, &mut rng);
+
+        // Multiplying by -1, which isn't a square, marks a card without
+        // changing the card it opens to, and the mark survives the shuffles.
+        let mut bytes = deck.to_bytes();
+        let minus_one = group.prime() - 1u8;
+        bytes[0] = ((&deck.cards()[0] * minus_one) % group.prime()).to_bytes_be();
+
+        assert_eq!(
+            MaskedDeck::from_bytes(group, &Deck::italian(), &bytes).err(),
+            Some(MentalPokerError::InvalidCards)
+        );
+    }
+
+    #[test]
+    fn keys_outside_of_the_group_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let group = small_group();
+        let mut deck = MaskedDeck::new(group.clone(), &Deck::italian()).unwrap();
+        let mut player = Participant::new(group.clone());
+        player.shuffle(&mut deck, &mut rng);
+        player.lock(&mut deck, &mut rng).unwrap();
+
+        let q: BigUint = group.prime() >> 1;
+        assert_eq!(
+            CardKey::from_bytes(&group, 0, &[0]),
+            Err(MentalPokerError::InvalidKey)
+        );
+        assert_eq!(
+            CardKey::from_bytes(&group, 0, &q.to_bytes_be()),
+            Err(MentalPokerError::InvalidKey)
+        );
+        let key = CardKey {
+            position: 0,
+            decrypt: q,
+        };
+        assert_eq!(deck.open(0, &[key]), Err(MentalPokerError::InvalidKey));
+    }
+
+    #[test]
+    fn decks_are_locked_after_shuffling() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut deck = MaskedDeck::new(small_group(), &Deck::italian()).unwrap();
+        let mut player = Participant::new(small_group());
+
+        assert_eq!(
+            player.lock(&mut deck, &mut rng),
+            Err(MentalPokerError::NotShuffled)
+        );
+        assert!(player.card_key(0).is_none());
+    }
+}
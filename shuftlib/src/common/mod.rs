@@ -1,4 +1,26 @@
+/// Auctions opening the hands of games where players bid for a contract.
+pub mod auction;
+/// Lookup tables indexed by card.
+pub mod card_map;
+/// Compact sets of cards, backed by a bitset.
+pub mod card_set;
 /// Common cards and decks types.
 pub mod cards;
-/// Common utility types to define tricks, hands, players.
+/// Distribution of the cards of a deck to the players.
+pub mod dealing;
+/// Log of the events of a match, and their replay.
+pub mod events;
+/// Commit-reveal protocol to prove a deck has been shuffled fairly.
+pub mod fairness;
+/// Whole matches of trick taking games, played by teams of players.
+pub mod game;
+/// Common utility types to define tricks, hands, players and teams.
 pub mod hands;
+/// Dealing without a trusted dealer, by means of commutative encryption.
+pub mod mental_poker;
+/// Text rendering of cards, tricks and hands.
+pub mod render;
+/// Scores of the hands and of whole matches.
+pub mod scoring;
+/// What a single player is allowed to know about a match.
+pub mod view;
@@ -0,0 +1,701 @@
+use std::fmt::{self, Display, Formatter};
+
+use unicode_width::UnicodeWidthStr;
+
+use super::{
+    cards::{
+        FrenchCard, FrenchRank, FrenchWithJoker, ItalianCard, ItalianRank, Joker, Suit, Suited,
+    },
+    hands::{Hand, OngoingTrick, PlayerId, Trick, TrickTakingGame},
+};
+
+/// Writes a single card. Formats are plain values, so that the same tricks
+/// can be shown differently to a terminal and to a log.
+pub trait CardFormat<C> {
+    /// Writes `card` to `f`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing to `f` fails.
+    fn fmt_card(&self, card: &C, f: &mut Formatter<'_>) -> fmt::Result;
+}
+
+/// Writes cards in the same notation used by their `Display` implementation,
+/// such as "1H".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Plain;
+
+impl<C: Display> CardFormat<C> for Plain {
+    fn fmt_card(&self, card: &C, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", card)
+    }
+}
+
+/// Writes cards with Unicode symbols, see `Glyph`.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{cards::{FrenchCard, FrenchRank, ItalianCard, ItalianRank, Suit}, render::{Glyphs, Renderer}};
+///
+/// let renderer = Renderer::new(Glyphs);
+/// let queen = FrenchCard::new(FrenchRank::Queen, Suit::Hearts);
+/// let ace = ItalianCard::new(ItalianRank::Ace, Suit::Clubs);
+///
+/// assert_eq!(renderer.card(&queen).to_string(), "🂽");
+/// assert_eq!(renderer.card(&ace).to_string(), "A🪵");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Glyphs;
+
+impl<C: Glyph> CardFormat<C> for Glyphs {
+    fn fmt_card(&self, card: &C, f: &mut Formatter<'_>) -> fmt::Result {
+        card.fmt_glyph(f)
+    }
+}
+
+/// Cards that can be written with Unicode symbols rather than letters.
+pub trait Glyph {
+    /// Writes the card with Unicode symbols.
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing to `f` fails.
+    fn fmt_glyph(&self, f: &mut Formatter<'_>) -> fmt::Result;
+}
+
+/// The initial of the Italian rank followed by a symbol for the suit: 🍷 for
+/// coppe, 🪙 for denari, 🪵 for bastoni and 🗡 for spade, such as "R🪙" for
+/// the king of denari.
+impl Glyph for ItalianCard {
+    fn fmt_glyph(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rank = match self.rank() {
+            ItalianRank::Ace => "A".to_string(),
+            ItalianRank::Jack => "F".to_string(),
+            ItalianRank::Knight => "C".to_string(),
+            ItalianRank::King => "R".to_string(),
+            rank => rank.to_string(),
+        };
+        let suit = match self.suit() {
+            Suit::Hearts => "🍷",
+            Suit::Diamonds => "🪙",
+            Suit::Clubs => "🪵",
+            Suit::Spades => "🗡",
+        };
+        write!(f, "{}{}", rank, suit)
+    }
+}
+
+/// The character of the Unicode Playing Cards block, such as 🂡 for the ace
+/// of spades.
+impl Glyph for FrenchCard {
+    fn fmt_glyph(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let suit = match self.suit() {
+            Suit::Spades => 0x1F0A0,
+            Suit::Hearts => 0x1F0B0,
+            Suit::Diamonds => 0x1F0C0,
+            Suit::Clubs => 0x1F0D0,
+        };
+        // The block has a knight between the jack and the queen.
+        let rank = match self.rank() {
+            FrenchRank::Queen | FrenchRank::King => self.rank() as u32 + 1,
+            rank => rank as u32,
+        };
+
+        match char::from_u32(suit + rank) {
+            Some(c) => write!(f, "{}", c),
+            None => write!(f, "{}", self),
+        }
+    }
+}
+
+/// Jokers are written as 🃏.
+impl Glyph for Joker {
+    fn fmt_glyph(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "🃏")
+    }
+}
+
+impl Glyph for FrenchWithJoker {
+    fn fmt_glyph(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FrenchWithJoker::Normal(c) => c.fmt_glyph(f),
+            FrenchWithJoker::Joker(j) => j.fmt_glyph(f),
+        }
+    }
+}
+
+/// The colors of a terminal supporting ANSI escape sequences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AnsiColor {
+    /// The default color of the terminal.
+    #[default]
+    Default,
+    /// Black.
+    Black,
+    /// Red.
+    Red,
+    /// Green.
+    Green,
+    /// Yellow.
+    Yellow,
+    /// Blue.
+    Blue,
+    /// Magenta.
+    Magenta,
+    /// Cyan.
+    Cyan,
+    /// White.
+    White,
+}
+
+impl AnsiColor {
+    /// Returns the SGR parameter setting this color as foreground.
+    fn code(&self) -> u8 {
+        match self {
+            AnsiColor::Default => 39,
+            AnsiColor::Black => 30,
+            AnsiColor::Red => 31,
+            AnsiColor::Green => 32,
+            AnsiColor::Yellow => 33,
+            AnsiColor::Blue => 34,
+            AnsiColor::Magenta => 35,
+            AnsiColor::Cyan => 36,
+            AnsiColor::White => 37,
+        }
+    }
+}
+
+/// Colors the cards written by another format with ANSI escape sequences,
+/// picking the color by suit. Cards without a suit, like jokers, aren't
+/// colored.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{cards::{ItalianCard, ItalianRank, Suit}, render::{Ansi, Glyphs, Plain, Renderer}};
+///
+/// let seven = ItalianCard::new(ItalianRank::Seven, Suit::Diamonds);
+///
+/// let french = Renderer::new(Ansi::french(Plain));
+/// assert_eq!(french.card(&seven).to_string(), "\x1b[31m7D\x1b[0m");
+///
+/// let italian = Renderer::new(Ansi::italian(Glyphs));
+/// assert_eq!(italian.card(&seven).to_string(), "\x1b[33m7🪙\x1b[0m");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ansi<F> {
+    format: F,
+    colors: [AnsiColor; 4],
+}
+
+impl<F> Ansi<F> {
+    /// Colors hearts and diamonds in red, leaving the other suits in the
+    /// default color of the terminal.
+    pub fn french(format: F) -> Self {
+        Self {
+            format,
+            colors: [
+                AnsiColor::Red,
+                AnsiColor::Red,
+                AnsiColor::Default,
+                AnsiColor::Default,
+            ],
+        }
+    }
+
+    /// Colors the suits as printed on Italian cards: coppe in red, denari in
+    /// yellow, bastoni in green and spade in blue.
+    pub fn italian(format: F) -> Self {
+        Self {
+            format,
+            colors: [
+                AnsiColor::Red,
+                AnsiColor::Yellow,
+                AnsiColor::Green,
+                AnsiColor::Blue,
+            ],
+        }
+    }
+
+    /// Sets the color of the cards of `suit`.
+    pub fn with_color(mut self, suit: Suit, color: AnsiColor) -> Self {
+        self.colors[suit as usize] = color;
+        self
+    }
+
+    /// Returns the color of the cards of `suit`.
+    pub fn color(&self, suit: Suit) -> AnsiColor {
+        self.colors[suit as usize]
+    }
+
+    fn fmt_colored<C>(&self, card: &C, suit: Option<Suit>, f: &mut Formatter<'_>) -> fmt::Result
+    where
+        F: CardFormat<C>,
+    {
+        match suit {
+            Some(suit) => {
+                write!(f, "\x1b[{}m", self.color(suit).code())?;
+                self.format.fmt_card(card, f)?;
+                write!(f, "\x1b[0m")
+            }
+            None => self.format.fmt_card(card, f),
+        }
+    }
+}
+
+impl<C, F> CardFormat<C> for Ansi<F>
+where
+    C: Suited,
+    F: CardFormat<C>,
+{
+    fn fmt_card(&self, card: &C, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_colored(card, Some(card.suit()), f)
+    }
+}
+
+impl<F> CardFormat<FrenchWithJoker> for Ansi<F>
+where
+    F: CardFormat<FrenchWithJoker>,
+{
+    fn fmt_card(&self, card: &FrenchWithJoker, f: &mut Formatter<'_>) -> fmt::Result {
+        let suit = match card {
+            FrenchWithJoker::Normal(c) => Some(c.suit()),
+            FrenchWithJoker::Joker(_) => None,
+        };
+        self.fmt_colored(card, suit, f)
+    }
+}
+
+/// How tricks and hands are laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// A single line per trick, with the cards ordered by `PlayerId`. The card
+    /// of the player who played first is preceded by `>` and the one the
+    /// trick has been taken with is followed by `*`, such as ">1H 2H 3H* 4H".
+    #[default]
+    Compact,
+    /// A table with a row for each player of a trick, or a column for each
+    /// player of a hand, marking who played first and who took the trick.
+    Table,
+}
+
+/// Renders cards, tricks and hands as text, writing cards with a
+/// `CardFormat` and laying them out with a `Layout`. Cards that haven't been
+/// played yet are written as `--`.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{hands::Trick, render::{Glyphs, Layout, Renderer}};
+/// use shuftlib::tressette::TressetteRules;
+///
+/// let trick: Trick<TressetteRules> = "1H 2H 3H 4H 1 2".parse().unwrap();
+///
+/// let compact = Renderer::default();
+/// assert_eq!(compact.trick(&trick).to_string(), "1H >2H 3H* 4H");
+///
+/// let table = Renderer::new(Glyphs).with_layout(Layout::Table);
+/// assert_eq!(
+///     table.trick(&trick).to_string(),
+///     "Player  Card\n\
+///      0       A🍷\n\
+///      1       2🍷   first\n\
+///      2       3🍷   takes\n\
+///      3       4🍷\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Renderer<F = Plain> {
+    format: F,
+    layout: Layout,
+}
+
+/// Writes cards with `Plain`, in the compact layout.
+impl Default for Renderer<Plain> {
+    fn default() -> Self {
+        Self::new(Plain)
+    }
+}
+
+impl<F> Renderer<F> {
+    /// Creates a renderer writing cards with `format`, in the compact layout.
+    pub fn new(format: F) -> Self {
+        Self {
+            format,
+            layout: Layout::default(),
+        }
+    }
+
+    /// Sets the layout of tricks and hands.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Returns the format cards are written with.
+    pub fn format(&self) -> &F {
+        &self.format
+    }
+
+    /// Returns the layout of tricks and hands.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Renders a single card.
+    pub fn card<'a, C>(&'a self, card: &'a C) -> impl Display + 'a
+    where
+        F: CardFormat<C>,
+    {
+        Rendered(move |f: &mut Formatter<'_>| self.format.fmt_card(card, f))
+    }
+
+    /// Renders some cards, such as the hand of a player, on a single line.
+    pub fn cards<'a, C>(&'a self, cards: &'a [C]) -> impl Display + 'a
+    where
+        F: CardFormat<C>,
+    {
+        Rendered(move |f: &mut Formatter<'_>| {
+            let cards: Vec<String> = cards.iter().map(|c| self.card(c).to_string()).collect();
+            write!(f, "{}", cards.join(" "))
+        })
+    }
+
+    /// Renders a trick that has been taken.
+    pub fn trick<'a, G>(&'a self, trick: &'a Trick<G>) -> impl Display + 'a
+    where
+        G: TrickTakingGame,
+        F: CardFormat<G::CardType>,
+    {
+        Rendered(move |f: &mut Formatter<'_>| {
+            let seats = self.seats(
+                trick.cards().iter().map(Some),
+                trick.first_to_play(),
+                Some(trick.taker()),
+            );
+            self.fmt_trick(&seats, f)
+        })
+    }
+
+    /// Renders a trick that's still being played.
+    pub fn ongoing_trick<'a, G>(&'a self, trick: &'a OngoingTrick<G>) -> impl Display + 'a
+    where
+        G: TrickTakingGame,
+        F: CardFormat<G::CardType>,
+    {
+        Rendered(move |f: &mut Formatter<'_>| {
+            let seats = self.seats(
+                trick.cards().iter().map(Option::as_ref),
+                Some(trick.first_to_play()),
+                None,
+            );
+            self.fmt_trick(&seats, f)
+        })
+    }
+
+    /// Renders every trick of a hand, in the order they have been played.
+    pub fn hand<'a, G>(&'a self, hand: &'a Hand<G>) -> impl Display + 'a
+    where
+        G: TrickTakingGame,
+        F: CardFormat<G::CardType>,
+    {
+        Rendered(move |f: &mut Formatter<'_>| {
+            let tricks: Vec<Vec<Seat>> = hand
+                .tricks()
+                .iter()
+                .map(|t| {
+                    self.seats(
+                        t.cards().iter().map(Some),
+                        t.first_to_play(),
+                        Some(t.taker()),
+                    )
+                })
+                .collect();
+
+            match self.layout {
+                Layout::Compact => {
+                    for (i, seats) in tricks.iter().enumerate() {
+                        write!(f, "{}: ", i + 1)?;
+                        fmt_compact(seats, f)?;
+                        writeln!(f)?;
+                    }
+                    Ok(())
+                }
+                Layout::Table => fmt_hand_table(&tricks, f),
+            }
+        })
+    }
+
+    fn seats<'a, C: 'a>(
+        &self,
+        cards: impl Iterator<Item = Option<&'a C>>,
+        first_to_play: Option<PlayerId>,
+        taker: Option<PlayerId>,
+    ) -> Vec<Seat>
+    where
+        F: CardFormat<C>,
+    {
+        cards
+            .enumerate()
+            .map(|(id, card)| Seat {
+                card: card.map_or_else(|| "--".to_string(), |c| self.card(c).to_string()),
+                first: Some(id) == first_to_play.map(usize::from),
+                taker: Some(id) == taker.map(usize::from),
+            })
+            .collect()
+    }
+
+    fn fmt_trick(&self, seats: &[Seat], f: &mut Formatter<'_>) -> fmt::Result {
+        match self.layout {
+            Layout::Compact => fmt_compact(seats, f),
+            Layout::Table => fmt_trick_table(seats, f),
+        }
+    }
+}
+
+/// A card of a trick, already written, with the role of who played it.
+struct Seat {
+    card: String,
+    first: bool,
+    taker: bool,
+}
+
+impl Seat {
+    fn marked(&self) -> String {
+        format!(
+            "{}{}{}",
+            if self.first { ">" } else { "" },
+            self.card,
+            if self.taker { "*" } else { "" }
+        )
+    }
+}
+
+fn fmt_compact(seats: &[Seat], f: &mut Formatter<'_>) -> fmt::Result {
+    let cards: Vec<String> = seats.iter().map(Seat::marked).collect();
+    write!(f, "{}", cards.join(" "))
+}
+
+fn fmt_trick_table(seats: &[Seat], f: &mut Formatter<'_>) -> fmt::Result {
+    let width = width(seats.iter().map(|s| s.card.as_str()).chain(["Card"]));
+    writeln!(f, "Player  {}", pad("Card", width).trim_end())?;
+    for (id, seat) in seats.iter().enumerate() {
+        let role = match (seat.first, seat.taker) {
+            (true, true) => "first, takes",
+            (true, false) => "first",
+            (false, true) => "takes",
+            (false, false) => "",
+        };
+        let line = format!("{:<6}  {}  {}", id, pad(&seat.card, width), role);
+        writeln!(f, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+fn fmt_hand_table(tricks: &[Vec<Seat>], f: &mut Formatter<'_>) -> fmt::Result {
+    let players = tricks.iter().map(Vec::len).max().unwrap_or_default();
+    let cells: Vec<Vec<String>> = tricks
+        .iter()
+        .map(|seats| seats.iter().map(Seat::marked).collect())
+        .collect();
+    let headers: Vec<String> = (0..players).map(|id| format!("P{}", id)).collect();
+    let width = width(cells.iter().flatten().chain(&headers).map(String::as_str));
+
+    let header: Vec<String> = headers.iter().map(|h| pad(h, width)).collect();
+    writeln!(f, "{}", format!("Trick  {}", header.join("  ")).trim_end())?;
+    for (i, row) in cells.iter().enumerate() {
+        let row: Vec<String> = row.iter().map(|c| pad(c, width)).collect();
+        writeln!(
+            f,
+            "{}",
+            format!("{:<5}  {}", i + 1, row.join("  ")).trim_end()
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the width of the widest of `cells`, see `visible_width`.
+fn width<'a>(cells: impl Iterator<Item = &'a str>) -> usize {
+    cells.map(visible_width).max().unwrap_or_default()
+}
+
+/// Returns the amount of terminal columns taken by `cell`, not counting the
+/// ANSI escape sequences written by `Ansi`. Emoji like 🍷 take two columns.
+fn visible_width(cell: &str) -> usize {
+    let mut visible = String::with_capacity(cell.len());
+    let mut chars = cell.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            visible.push(c);
+        }
+    }
+    visible.width()
+}
+
+/// Pads `cell` with spaces up to `width`, see `visible_width`.
+fn pad(cell: &str, width: usize) -> String {
+    let padding = width.saturating_sub(visible_width(cell));
+    format!("{}{}", cell, " ".repeat(padding))
+}
+
+/// Displays what a closure writes.
+struct Rendered<W>(W);
+
+impl<W> Display for Rendered<W>
+where
+    W: Fn(&mut Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        common::{
+            cards::{
+                FrenchCard, FrenchRank, FrenchWithJoker, IndexedCard, ItalianCard, Joker,
+                ShuffleSeed, Suit,
+            },
+            game::Match,
+            hands::{OngoingTrick, Trick, TrickTakingGame},
+        },
+        tressette::{TressetteCard, TressetteRules},
+    };
+
+    use super::{Ansi, AnsiColor, Glyphs, Layout, Renderer};
+
+    #[test]
+    fn french_cards_are_in_the_playing_cards_block() {
+        let renderer = Renderer::new(Glyphs);
+        let card = |rank, suit| renderer.card(&FrenchCard::new(rank, suit)).to_string();
+
+        assert_eq!(card(FrenchRank::Ace, Suit::Spades), "🂡");
+        assert_eq!(card(FrenchRank::Ten, Suit::Hearts), "🂺");
+        assert_eq!(card(FrenchRank::Jack, Suit::Diamonds), "🃋");
+        assert_eq!(card(FrenchRank::Queen, Suit::Clubs), "🃝");
+        assert_eq!(card(FrenchRank::King, Suit::Spades), "🂮");
+        assert_eq!(
+            renderer
+                .card(&FrenchWithJoker::Joker(Joker::default()))
+                .to_string(),
+            "🃏"
+        );
+    }
+
+    #[test]
+    fn colors_are_picked_by_suit() {
+        let renderer =
+            Renderer::new(Ansi::french(Glyphs).with_color(Suit::Clubs, AnsiColor::Green));
+        let card = |rank, suit| renderer.card(&FrenchCard::new(rank, suit)).to_string();
+
+        assert_eq!(card(FrenchRank::Two, Suit::Diamonds), "\x1b[31m🃂\x1b[0m");
+        assert_eq!(card(FrenchRank::Two, Suit::Clubs), "\x1b[32m🃒\x1b[0m");
+        assert_eq!(card(FrenchRank::Two, Suit::Spades), "\x1b[39m🂢\x1b[0m");
+        assert_eq!(
+            renderer
+                .card(&FrenchWithJoker::Joker(Joker::default()))
+                .to_string(),
+            "🃏"
+        );
+    }
+
+    #[test]
+    fn colors_dont_break_tables() {
+        let trick: Trick<TressetteRules> = "1S 10D 3C 7H 2 1".parse().unwrap();
+        let plain = Renderer::default().with_layout(Layout::Table);
+        let colored = Renderer::new(Ansi::italian(super::Plain)).with_layout(Layout::Table);
+
+        let plain = plain.trick(&trick).to_string();
+        let colored = colored.trick(&trick).to_string();
+        assert_ne!(plain, colored);
+        assert_eq!(super::visible_width(&colored), plain.chars().count());
+        for (plain, colored) in plain.lines().zip(colored.lines()) {
+            assert_eq!(super::visible_width(colored), plain.chars().count());
+        }
+    }
+
+    #[test]
+    fn emoji_take_two_columns() {
+        let trick: Trick<TressetteRules> = "10D 4H 7C 1S 0 0".parse().unwrap();
+        let table = Renderer::new(Glyphs).with_layout(Layout::Table);
+
+        assert_eq!(
+            table.trick(&trick).to_string(),
+            "Player  Card\n\
+             0       R🪙   first, takes\n\
+             1       4🍷\n\
+             2       7🪵\n\
+             3       A🗡\n"
+        );
+    }
+
+    #[test]
+    fn ongoing_tricks_show_the_missing_cards() {
+        let rules = TressetteRules::default();
+        let mut trick = OngoingTrick::new(&rules, rules.player(3).unwrap()).unwrap();
+        trick.play("7S".parse::<TressetteCard>().unwrap()).unwrap();
+        trick.play("1S".parse::<TressetteCard>().unwrap()).unwrap();
+
+        let renderer = Renderer::default();
+        assert_eq!(renderer.ongoing_trick(&trick).to_string(), "1S -- -- >7S");
+        let table = renderer.with_layout(Layout::Table);
+        assert_eq!(
+            table.ongoing_trick(&trick).to_string(),
+            "Player  Card\n0       1S\n1       --\n2       --\n3       7S    first\n"
+        );
+    }
+
+    #[test]
+    fn hands_have_a_line_per_trick() {
+        let mut tressette = Match::new(TressetteRules::default(), ShuffleSeed::from(2)).unwrap();
+        while tressette.hands().is_empty() {
+            let action = tressette.legal_moves()[0];
+            tressette.apply(action).unwrap();
+        }
+        let hand = &tressette.hands()[0];
+
+        let compact = Renderer::default().hand(hand).to_string();
+        assert_eq!(compact.lines().count(), TressetteRules::TRICKS);
+        assert!(compact.starts_with("1: "));
+
+        let table = Renderer::new(Glyphs).with_layout(Layout::Table);
+        let table = table.hand(hand).to_string();
+        let mut lines = table.lines();
+        assert_eq!(lines.next().unwrap().split_whitespace().count(), 5);
+        for line in lines {
+            assert_eq!(line.split_whitespace().count(), 5);
+            assert_eq!(line.matches('>').count(), 1);
+            assert_eq!(line.matches('*').count(), 1);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn tricks_of_any_size_are_rendered(indexes in proptest::collection::hash_set(0..ItalianCard::COUNT, 1..=8), first: usize, taker: usize) {
+            let cards: Vec<String> = indexes
+                .iter()
+                .map(|&i| TressetteCard::from_index(i).unwrap().to_string())
+                .collect();
+            let notation = format!("{} {} {}", cards.join(" "), first % cards.len(), taker % cards.len());
+            let trick: Trick<TressetteRules> = notation.parse().unwrap();
+            prop_assert_eq!(trick.to_string(), notation);
+
+            for renderer in [Renderer::default(), Renderer::default().with_layout(Layout::Table)] {
+                let rendered = renderer.trick(&trick).to_string();
+                for card in &cards {
+                    prop_assert!(rendered.contains(card.as_str()));
+                }
+            }
+
+            let glyphs = Renderer::new(Glyphs).trick(&trick).to_string();
+            prop_assert_eq!(glyphs.split(' ').count(), cards.len());
+            prop_assert_eq!(glyphs.matches('>').count(), 1);
+            prop_assert_eq!(glyphs.matches('*').count(), 1);
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use super::hands::{Hand, TeamId, Teams, TrickTakingGame};
+
+/// How the score of a match is kept: the points made by each team in a hand
+/// and the points needed to win the match.
+pub trait Scoring: TrickTakingGame {
+    /// Returns the points made in `hand` by each of the teams the score of
+    /// the match is kept for. `teams` are the teams that played the hand,
+    /// which might not be the same for every hand.
+    fn score_hand(&self, hand: &Hand<Self>, teams: &Teams) -> HandScore;
+
+    /// Returns the score a team has to reach to win the match, and what
+    /// happens if more teams reach it.
+    fn target(&self) -> Target;
+}
+
+/// The points made by a team in a single hand, split by where they come
+/// from. Only the points of the contract can be negative, when it's lost.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::scoring::ScoreBreakdown;
+///
+/// let points = ScoreBreakdown::default().with_cards(7).with_last_trick(1);
+/// assert_eq!(points.total(), 8);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreBreakdown {
+    cards: u32,
+    last_trick: u32,
+    declarations: u32,
+    contract: i32,
+}
+
+impl ScoreBreakdown {
+    /// Sets the points made with the cards taken.
+    pub fn with_cards(mut self, points: u32) -> Self {
+        self.cards = points;
+        self
+    }
+
+    /// Sets the bonus for taking the last trick of the hand.
+    pub fn with_last_trick(mut self, points: u32) -> Self {
+        self.last_trick = points;
+        self
+    }
+
+    /// Sets the points made by declaring combinations of cards.
+    pub fn with_declarations(mut self, points: u32) -> Self {
+        self.declarations = points;
+        self
+    }
+
+    /// Sets the points won or lost with the contract of the hand, in games
+    /// where players bid for it.
+    pub fn with_contract(mut self, points: i32) -> Self {
+        self.contract = points;
+        self
+    }
+
+    /// Returns the points made with the cards taken.
+    pub fn cards(&self) -> u32 {
+        self.cards
+    }
+
+    /// Returns the bonus for taking the last trick of the hand.
+    pub fn last_trick(&self) -> u32 {
+        self.last_trick
+    }
+
+    /// Returns the points made by declaring combinations of cards.
+    pub fn declarations(&self) -> u32 {
+        self.declarations
+    }
+
+    /// Returns the points won or lost with the contract of the hand.
+    pub fn contract(&self) -> i32 {
+        self.contract
+    }
+
+    /// Returns the points made in the hand, which are negative if more
+    /// points have been lost with the contract than made otherwise.
+    pub fn total(&self) -> i64 {
+        i64::from(self.cards)
+            + i64::from(self.last_trick)
+            + i64::from(self.declarations)
+            + i64::from(self.contract)
+    }
+}
+
+/// The points made by every team in a single hand, indexed by `TeamId`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandScore {
+    teams: Vec<ScoreBreakdown>,
+}
+
+impl HandScore {
+    /// Creates the score of a hand, where `teams[N]` are the points made by
+    /// team N.
+    pub fn new(teams: Vec<ScoreBreakdown>) -> Self {
+        Self { teams }
+    }
+
+    /// Returns the points made by every team, ordered by `TeamId`.
+    pub fn teams(&self) -> &[ScoreBreakdown] {
+        &self.teams
+    }
+
+    /// Returns the points made by `team`. Teams missing from the score made
+    /// no points.
+    pub fn team(&self, team: TeamId) -> ScoreBreakdown {
+        self.teams.get(*team).copied().unwrap_or_default()
+    }
+}
+
+/// The score of a whole match: the points made by every team in each hand
+/// played, and their running totals.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{hands::TeamId, scoring::{HandScore, MatchScore, ScoreBreakdown}};
+///
+/// let mut score = MatchScore::default();
+/// score.add(HandScore::new(vec![
+///     ScoreBreakdown::default().with_cards(6),
+///     ScoreBreakdown::default().with_cards(4).with_last_trick(1),
+/// ]));
+/// score.add(HandScore::new(vec![ScoreBreakdown::default().with_cards(3)]));
+///
+/// assert_eq!(score.hands().len(), 2);
+/// assert_eq!(score.totals(), &[9, 5]);
+/// assert_eq!(score.total(TeamId::from(2)), 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchScore {
+    hands: Vec<HandScore>,
+    totals: Vec<i64>,
+}
+
+impl MatchScore {
+    /// Adds the points made in a hand.
+    pub fn add(&mut self, hand: HandScore) {
+        if self.totals.len() < hand.teams.len() {
+            self.totals.resize(hand.teams.len(), 0);
+        }
+        self.totals
+            .iter_mut()
+            .zip(&hand.teams)
+            .for_each(|(total, points)| *total += points.total());
+        self.hands.push(hand);
+    }
+
+    /// Returns the points made in every hand, in order.
+    pub fn hands(&self) -> &[HandScore] {
+        &self.hands
+    }
+
+    /// Returns the points made by every team so far, ordered by `TeamId`.
+    pub fn totals(&self) -> &[i64] {
+        &self.totals
+    }
+
+    /// Returns the points made by `team` so far.
+    pub fn total(&self, team: TeamId) -> i64 {
+        self.totals.get(*team).copied().unwrap_or_default()
+    }
+
+    /// Returns the teams with the most points, ordered by `TeamId`.
+    pub fn leaders(&self) -> Vec<TeamId> {
+        let max = self.totals.iter().max();
+        self.totals
+            .iter()
+            .enumerate()
+            .filter(|&(_, total)| Some(total) == max)
+            .map(|(team, _)| TeamId::from(team))
+            .collect()
+    }
+}
+
+/// What happens when more than one team reaches the target with the same,
+/// highest, score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TieBreak {
+    /// Another hand is played, until one team is ahead.
+    #[default]
+    PlayOn,
+    /// The match ends in a draw.
+    Draw,
+    /// The team that took the last trick of the last hand wins, if it's one of
+    /// the tied teams. Otherwise another hand is played.
+    LastTrick,
+}
+
+/// How a match ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    /// The match has been won by a team.
+    Won(TeamId),
+    /// The match ended in a draw.
+    Draw,
+}
+
+/// The score a team has to reach to win a match. When it's reached, the team
+/// with the most points wins.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{hands::TeamId, scoring::{HandScore, MatchScore, Outcome, ScoreBreakdown, Target, TieBreak}};
+///
+/// let mut score = MatchScore::default();
+/// score.add(HandScore::new(vec![
+///     ScoreBreakdown::default().with_cards(10),
+///     ScoreBreakdown::default().with_cards(9).with_last_trick(1),
+/// ]));
+///
+/// assert_eq!(Target::new(11).outcome(&score), None);
+/// assert_eq!(Target::new(10).outcome(&score), None);
+/// assert_eq!(Target::new(10).with_tie_break(TieBreak::Draw).outcome(&score), Some(Outcome::Draw));
+/// assert_eq!(
+///     Target::new(10).with_tie_break(TieBreak::LastTrick).outcome(&score),
+///     Some(Outcome::Won(TeamId::from(1)))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Target {
+    points: u32,
+    tie_break: TieBreak,
+}
+
+impl Target {
+    /// Creates a target of `points` points. Ties are broken by playing on.
+    pub fn new(points: u32) -> Self {
+        Self {
+            points,
+            tie_break: TieBreak::default(),
+        }
+    }
+
+    /// Sets what happens when more teams reach the target with the same
+    /// score.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Returns the points a team has to reach.
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+
+    /// Returns what happens when more teams reach the target with the same
+    /// score.
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    /// Returns how a match with the given score ended, or None if it isn't
+    /// over yet.
+    pub fn outcome(&self, score: &MatchScore) -> Option<Outcome> {
+        let leaders = score.leaders();
+        let &first = leaders.first()?;
+        if score.total(first) < i64::from(self.points) {
+            return None;
+        }
+        if leaders.len() == 1 {
+            return Some(Outcome::Won(first));
+        }
+
+        match self.tie_break {
+            TieBreak::PlayOn => None,
+            TieBreak::Draw => Some(Outcome::Draw),
+            TieBreak::LastTrick => {
+                let last_hand = score.hands().last()?;
+                leaders
+                    .into_iter()
+                    .find(|&team| last_hand.team(team).last_trick() > 0)
+                    .map(Outcome::Won)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{HandScore, MatchScore, Outcome, ScoreBreakdown, Target, TieBreak};
+
+    fn score(totals: &[u32]) -> MatchScore {
+        let mut score = MatchScore::default();
+        score.add(HandScore::new(
+            totals
+                .iter()
+                .map(|&t| ScoreBreakdown::default().with_cards(t))
+                .collect(),
+        ));
+        score
+    }
+
+    proptest! {
+        #[test]
+        fn nobody_wins_below_the_target(a in 0u32..31, b in 0u32..31) {
+            prop_assert_eq!(Target::new(31).outcome(&score(&[a, b])), None);
+        }
+
+        #[test]
+        fn ties_above_the_target_are_broken(points in 31u32..200, teams in 2usize..6) {
+            let score = score(&vec![points; teams]);
+            prop_assert_eq!(Target::new(31).outcome(&score), None);
+            prop_assert_eq!(
+                Target::new(31).with_tie_break(TieBreak::Draw).outcome(&score),
+                Some(Outcome::Draw)
+            );
+            prop_assert_eq!(
+                Target::new(31).with_tie_break(TieBreak::LastTrick).outcome(&score),
+                None
+            );
+        }
+
+        #[test]
+        fn the_team_ahead_wins(points in 31u32..200, behind in 0u32..200, leader in 0usize..4) {
+            prop_assume!(behind < points);
+            let mut totals = vec![behind; 4];
+            totals[leader] = points;
+            let score = score(&totals);
+
+            let Some(Outcome::Won(team)) = Target::new(31).outcome(&score) else {
+                panic!("the match should be over");
+            };
+            prop_assert_eq!(*team, leader);
+        }
+
+        #[test]
+        fn lost_contracts_take_points_away(cards in 0u32..100, contract in -100i32..100) {
+            let points = ScoreBreakdown::default().with_cards(cards).with_contract(contract);
+            let mut score = MatchScore::default();
+            score.add(HandScore::new(vec![points]));
+
+            prop_assert_eq!(points.total(), i64::from(cards) + i64::from(contract));
+            prop_assert_eq!(score.totals(), &[points.total()]);
+            prop_assert_eq!(Target::new(1).outcome(&score).is_some(), points.total() >= 1);
+        }
+
+        #[test]
+        fn totals_add_up(hands in proptest::collection::vec(proptest::collection::vec(0u32..12, 0..5), 0..10)) {
+            let mut score = MatchScore::default();
+            for hand in &hands {
+                score.add(HandScore::new(
+                    hand.iter().map(|&p| ScoreBreakdown::default().with_cards(p)).collect(),
+                ));
+            }
+
+            prop_assert_eq!(score.hands().len(), hands.len());
+            let expected: u32 = hands.iter().flatten().sum();
+            prop_assert_eq!(score.totals().iter().sum::<i64>(), i64::from(expected));
+        }
+    }
+}
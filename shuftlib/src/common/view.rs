@@ -0,0 +1,357 @@
+use super::{
+    auction::Auction,
+    card_set::CardSet,
+    events::Event,
+    game::{Action, Match, MatchRules},
+    hands::{HandContext, OngoingTrick, PlayerId, Teams, Trick},
+    scoring::MatchScore,
+};
+
+/// What a single player knows about a `Match`: their own hand and everything
+/// that has been played face up, but neither the cards held by the other
+/// players nor the order of the deck. For the other players only the amount
+/// of cards they hold is known.
+///
+/// This is the only state meant to be sent to a player or given to a bot,
+/// since it's built without ever copying hidden information.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{cards::ShuffleSeed, game::Match, hands::TrickTakingGame};
+/// use shuftlib::tressette::TressetteRules;
+///
+/// let rules = TressetteRules::default();
+/// let tressette = Match::new(rules.clone(), ShuffleSeed::from(1)).unwrap();
+/// let view = tressette.view(rules.player(0).unwrap()).unwrap();
+///
+/// assert_eq!(view.hand(), tressette.players()[0].hand());
+/// assert_eq!(view.cards_left(), &[10, 10, 10, 10]);
+/// assert_eq!(view.legal_moves(), tressette.legal_moves());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "G::CardType: serde::Serialize, \
+                     <G::CardType as crate::common::cards::Ranked>::Rank: serde::Serialize, \
+                     G::Signal: serde::Serialize",
+        deserialize = "G::CardType: serde::Deserialize<'de>, \
+                       <G::CardType as crate::common::cards::Ranked>::Rank: serde::Deserialize<'de>, \
+                       G::Signal: serde::Deserialize<'de>"
+    ))
+)]
+pub struct PlayerView<G>
+where
+    G: MatchRules,
+{
+    player: PlayerId,
+    hand: CardSet<G::CardType>,
+    cards_left: Vec<usize>,
+    revealed: Vec<CardSet<G::CardType>>,
+    stock: usize,
+    face_up: Vec<G::CardType>,
+    dealer: PlayerId,
+    context: HandContext,
+    auction: Option<Auction<G::CardType>>,
+    tricks: Vec<Trick<G>>,
+    current_trick: Option<OngoingTrick<G>>,
+    played: CardSet<G::CardType>,
+    signals: Vec<(PlayerId, G::CardType, G::Signal)>,
+    teams: Teams,
+    score: MatchScore,
+    next_to_play: Option<PlayerId>,
+    legal_moves: Vec<Action<G>>,
+}
+
+impl<G> PlayerView<G>
+where
+    G: MatchRules,
+{
+    /// Returns the player this view belongs to.
+    pub fn player(&self) -> PlayerId {
+        self.player
+    }
+
+    /// Returns the cards held by the player.
+    pub fn hand(&self) -> CardSet<G::CardType> {
+        self.hand
+    }
+
+    /// Returns the amount of cards held by each player, ordered by `PlayerId`.
+    pub fn cards_left(&self) -> &[usize] {
+        &self.cards_left
+    }
+
+    /// Returns the cards each player is known to hold, ordered by `PlayerId`.
+    /// Besides the hand of the player, these are the cards the others drew in
+    /// the open, see `Dealing::with_open_draws`, the ones they declared and
+    /// still hold, and the whole hand of an opponent once they're the only
+    /// one who can hold the cards nobody has seen yet.
+    pub fn revealed(&self) -> &[CardSet<G::CardType>] {
+        &self.revealed
+    }
+
+    /// Returns the amount of cards left in the deck after dealing.
+    pub fn stock(&self) -> usize {
+        self.stock
+    }
+
+    /// Returns the cards turned face up when the hand was dealt.
+    pub fn face_up(&self) -> &[G::CardType] {
+        &self.face_up
+    }
+
+    /// Returns the player who dealt the current hand.
+    pub fn dealer(&self) -> PlayerId {
+        self.dealer
+    }
+
+    /// Returns the context of the current hand, such as its trump suit.
+    pub fn context(&self) -> &HandContext {
+        &self.context
+    }
+
+    /// Returns the auction opening the current hand, if the game has one.
+    /// Bids and calls are made in the open, so it's known to everyone.
+    pub fn auction(&self) -> Option<&Auction<G::CardType>> {
+        self.auction.as_ref()
+    }
+
+    /// Returns the tricks already taken in the current hand.
+    pub fn tricks(&self) -> &[Trick<G>] {
+        &self.tricks
+    }
+
+    /// Returns the trick being played, if the match isn't over.
+    pub fn current_trick(&self) -> Option<&OngoingTrick<G>> {
+        self.current_trick.as_ref()
+    }
+
+    /// Returns every card played so far in the current hand, current trick
+    /// included.
+    pub fn played(&self) -> CardSet<G::CardType> {
+        self.played
+    }
+
+    /// Returns the signals made in the current hand, in order, with the
+    /// players who made them and the cards they were made with. Signals are
+    /// meant for partners, but everyone at the table sees them.
+    pub fn signals(&self) -> &[(PlayerId, G::CardType, G::Signal)] {
+        &self.signals
+    }
+
+    /// Returns the teams playing the current hand, as far as they are known
+    /// to everyone.
+    pub fn teams(&self) -> &Teams {
+        &self.teams
+    }
+
+    /// Returns the score of the match.
+    pub fn score(&self) -> &MatchScore {
+        &self.score
+    }
+
+    /// Returns the player who has to make the next move, if the match isn't
+    /// over.
+    pub fn next_to_play(&self) -> Option<PlayerId> {
+        self.next_to_play
+    }
+
+    /// Returns true if the player has to make the next move.
+    pub fn is_my_turn(&self) -> bool {
+        self.next_to_play() == Some(self.player)
+    }
+
+    /// Returns the actions the player is allowed to make. It's empty if it's
+    /// not their turn.
+    pub fn legal_moves(&self) -> &[Action<G>] {
+        &self.legal_moves
+    }
+}
+
+impl<G> Match<G>
+where
+    G: MatchRules,
+{
+    /// Returns what `player` knows about this match, or None if they don't
+    /// sit at this table.
+    pub fn view(&self, player: PlayerId) -> Option<PlayerView<G>> {
+        let seat = self.players().get(*player).filter(|p| p.id() == player)?;
+        let hand = self.ongoing_hand();
+        let tricks: Vec<Trick<G>> = hand.tricks().iter().flatten().cloned().collect();
+        let current_trick = self.current_trick().cloned();
+
+        let played = tricks
+            .iter()
+            .flat_map(|t| t.cards().iter().copied())
+            .chain(
+                current_trick
+                    .iter()
+                    .flat_map(|t| t.cards().iter().flatten().copied()),
+            )
+            .collect();
+        let dealt = self
+            .events()
+            .iter()
+            .rposition(|e| matches!(e, Event::Dealt { .. }))
+            .unwrap_or_default();
+        let mut known = vec![CardSet::default(); self.players().len()];
+        if self.rules().dealing(self.dealer()).open_draws() {
+            for event in &self.events()[dealt..] {
+                if let Event::Drawn { player, card } = event {
+                    known[**player].insert(*card);
+                }
+            }
+        }
+        let signals = self.events()[dealt..]
+            .iter()
+            .filter_map(|e| match *e {
+                Event::CardPlayed {
+                    player,
+                    card,
+                    signal: Some(signal),
+                } => Some((player, card, signal)),
+                _ => None,
+            })
+            .collect();
+        for (player, cards) in self.declarations() {
+            known[**player] |= *cards;
+        }
+        let mut revealed: Vec<_> = self
+            .players()
+            .iter()
+            .zip(known)
+            .map(|(p, known)| {
+                if p.id() == player {
+                    p.hand()
+                } else {
+                    p.hand() & known
+                }
+            })
+            .collect();
+        self.deduce_hand(player, played, &mut revealed);
+
+        let legal_moves = if self.next_to_play() == Some(player) {
+            self.legal_moves()
+        } else {
+            Vec::new()
+        };
+
+        Some(PlayerView {
+            player,
+            hand: seat.hand(),
+            cards_left: self.players().iter().map(|p| p.hand().len()).collect(),
+            revealed,
+            stock: self.stock().len(),
+            face_up: self.face_up().to_vec(),
+            dealer: self.dealer(),
+            context: *self.context(),
+            auction: self.auction().cloned(),
+            tricks,
+            current_trick,
+            played,
+            signals,
+            teams: self.teams().clone(),
+            score: self.score().clone(),
+            next_to_play: self.next_to_play(),
+            legal_moves,
+        })
+    }
+
+    /// Reveals the hand of the only other player still holding unknown cards,
+    /// if the cards of the deck that `player` hasn't seen are exactly as many
+    /// as the ones that player holds: the stock, and any card left out of
+    /// the deal, would make them more.
+    fn deduce_hand(
+        &self,
+        player: PlayerId,
+        played: CardSet<G::CardType>,
+        revealed: &mut [CardSet<G::CardType>],
+    ) {
+        let deck = self.rules().deck();
+        let mut unseen: CardSet<G::CardType> = deck.iter().copied().collect();
+        if unseen.len() != deck.len() {
+            // Cards appearing more than once can't be told apart.
+            return;
+        }
+        unseen -= played;
+        unseen -= self.face_up().iter().copied().collect();
+        revealed.iter().for_each(|&known| unseen -= known);
+
+        let mut hidden = self
+            .players()
+            .iter()
+            .zip(revealed.iter())
+            .enumerate()
+            .filter(|&(_, (p, known))| p.id() != player && p.hand().len() > known.len());
+        let (Some((seat, (p, known))), None) = (hidden.next(), hidden.next()) else {
+            return;
+        };
+        if p.hand().len() - known.len() == unseen.len() {
+            revealed[seat] = *known | unseen;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        common::{
+            cards::ShuffleSeed,
+            game::Match,
+            hands::{PlayerId, TrickTakingGame},
+        },
+        tressette::TressetteRules,
+    };
+
+    #[test]
+    fn views_only_show_what_the_player_knows() {
+        let rules = TressetteRules::default();
+        let mut tressette = Match::new(rules.clone(), ShuffleSeed::from(11)).unwrap();
+        for _ in 0..6 {
+            let action = tressette.legal_moves()[0];
+            tressette.apply(action).unwrap();
+        }
+
+        for id in 0..rules.players() {
+            let player = rules.player(id).unwrap();
+            let view = tressette.view(player).unwrap();
+
+            assert_eq!(view.hand(), tressette.players()[id].hand());
+            assert_eq!(view.tricks().len(), 1);
+            assert_eq!(view.played().len(), 6);
+            assert!(view.hand().is_disjoint(view.played()));
+            assert_eq!(view.cards_left().iter().sum::<usize>(), 40 - 6);
+            assert_eq!(view.is_my_turn(), tressette.next_to_play() == Some(player));
+            assert_eq!(view.legal_moves().is_empty(), !view.is_my_turn());
+            assert_eq!(view.teams(), tressette.teams());
+        }
+
+        assert!(tressette.view(PlayerId::new(0, 2).unwrap()).is_none());
+    }
+
+    #[test]
+    fn hands_are_revealed_once_they_can_be_deduced() {
+        let rules = TressetteRules::new(2).unwrap();
+        let mut tressette = Match::new(rules.clone(), ShuffleSeed::from(11)).unwrap();
+        let (me, opponent) = (rules.player(0).unwrap(), rules.player(1).unwrap());
+
+        // Nothing is known about the cards dealt to the opponent.
+        assert!(tressette.view(me).unwrap().revealed()[1].is_empty());
+        while !tressette.stock().is_empty() {
+            let view = tressette.view(me).unwrap();
+            assert!(view.revealed()[1].is_subset(tressette.players()[1].hand()));
+
+            let action = tressette.legal_moves()[0];
+            tressette.apply(action).unwrap();
+        }
+
+        // Every card left is in the hands of the two players.
+        let view = tressette.view(me).unwrap();
+        assert_eq!(view.revealed()[1], tressette.players()[1].hand());
+        let view = tressette.view(opponent).unwrap();
+        assert_eq!(view.revealed()[0], tressette.players()[0].hand());
+    }
+}
@@ -1,21 +1,154 @@
-use std::{fmt::Display, ops::Deref};
+use std::{fmt::Display, ops::Deref, str::FromStr};
 
 use crate::common::{
-    cards::{Card, ItalianCard, ItalianRank, Suit},
-    hands::{Hand, OngoingTrick, Player, PlayerId, TrickTakingGame},
+    card_set::CardSet,
+    cards::{
+        Card, Deck, IndexedCard, ItalianCard, ItalianRank, ParseCardError, Ranked, Suit, Suited,
+    },
+    dealing::Dealing,
+    game::MatchRules,
+    hands::{Hand, HandContext, OngoingTrick, PlayError, Player, PlayerId, Teams, TrickTakingGame},
+    render::Glyph,
+    scoring::{HandScore, ScoreBreakdown, Scoring, Target},
 };
 use num_rational::Rational32;
 use std::cmp::Ordering;
+use strum::IntoEnumIterator;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Contains the rules of the tressette game. By default it's the four players
+/// game, where players 0 and 2 play against players 1 and 3 and every player
+/// receives all of their 10 cards at once.
+///
+/// In the two players game each player receives 10 cards too, but the other
+/// 20 are left as stock: after every trick the taker draws a card from it,
+/// then the other player does, and both cards are shown to the opponent. Once
+/// the stock is over the hand goes on until both players have played every
+/// card, and since each of them knows which cards are left, both hands are
+/// revealed, see `PlayerView::revealed`.
+///
+/// Variants of the game, such as the one with declarations, are chosen with
+/// `TressetteOptions`.
+///
+/// # Examples
+/// ```
+/// use shuftlib::common::{cards::ShuffleSeed, game::Match, hands::TrickTakingGame, scoring::Target};
+/// use shuftlib::tressette::TressetteRules;
+///
+/// let rules = TressetteRules::new(2).unwrap().with_target(Target::new(21));
+/// assert_eq!(rules.tricks(), 20);
+///
+/// let tressette = Match::new(rules, ShuffleSeed::from(1)).unwrap();
+/// assert_eq!(tressette.stock().len(), 20);
+/// ```
+pub struct TressetteRules {
+    players: usize,
+    target: Target,
+    options: TressetteOptions,
+}
+
+impl Default for TressetteRules {
+    /// The four players game, to 31 points.
+    fn default() -> Self {
+        Self {
+            players: Self::PLAYERS,
+            target: Target::new(SCORE_TO_WIN),
+            options: TressetteOptions::default(),
+        }
+    }
+}
 
-#[derive(Clone, Debug, Default)]
-/// Contains the rules of the tressette game.
-pub struct TressetteRules {}
+/// The variants of tressette a table plays with. By default none is enabled.
+///
+/// # Examples
+/// ```
+/// use shuftlib::tressette::{TressetteOptions, TressetteRules};
+///
+/// let options = TressetteOptions::default().with_declarations(true);
+/// let rules = TressetteRules::default().with_options(options);
+/// assert!(rules.options().declarations());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TressetteOptions {
+    declarations: bool,
+    signals: bool,
+}
+
+impl TressetteOptions {
+    /// Sets whether players can declare their combinations of Aces, Twos and
+    /// Threes during the first trick, see `Declaration`.
+    pub fn with_declarations(mut self, enabled: bool) -> Self {
+        self.declarations = enabled;
+        self
+    }
+
+    /// Returns true if players can declare their combinations.
+    pub fn declarations(&self) -> bool {
+        self.declarations
+    }
+
+    /// Sets whether partners can signal to each other in the four players
+    /// game, see `TressetteRules::legal_signals`. Tables playing "muto" don't
+    /// allow it.
+    pub fn with_signals(mut self, enabled: bool) -> Self {
+        self.signals = enabled;
+        self
+    }
+
+    /// Returns true if partners can signal to each other.
+    pub fn signals(&self) -> bool {
+        self.signals
+    }
+}
 
 impl TrickTakingGame for TressetteRules {
     type CardType = TressetteCard;
 
-    const PLAYERS: usize = 4;
-    const TRICKS: usize = 10;
+    fn players(&self) -> usize {
+        self.players
+    }
+
+    fn tricks(&self) -> usize {
+        ItalianCard::COUNT as usize / self.players
+    }
+
+    /// In tressette players have to follow the suit of the first card played
+    /// in the trick, as long as they hold a card of that suit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::{cards::{ItalianRank, Suit}, hands::{OngoingTrick, PlayError, Player, TrickTakingGame}};
+    /// use shuftlib::tressette::{TressetteCard, TressetteRules};
+    ///
+    /// let rules = TressetteRules::default();
+    /// let mut trick = OngoingTrick::new(&rules, rules.player(0).unwrap()).unwrap();
+    /// trick.play(TressetteCard::new(ItalianRank::Ace, Suit::Spades)).unwrap();
+    ///
+    /// let mut player = Player::new(rules.player(1).unwrap());
+    /// player.give(TressetteCard::new(ItalianRank::Two, Suit::Spades));
+    /// player.give(TressetteCard::new(ItalianRank::Ace, Suit::Hearts));
+    ///
+    /// let hearts = TressetteCard::new(ItalianRank::Ace, Suit::Hearts);
+    /// assert_eq!(rules.check_play(&player, hearts, &trick), Err(PlayError::MustFollowSuit));
+    /// ```
+    fn check_card(
+        &self,
+        hand: CardSet<TressetteCard>,
+        card: TressetteCard,
+        trick: &OngoingTrick<Self>,
+    ) -> Result<(), PlayError> {
+        match trick.lead() {
+            Some(lead)
+                if card.suit() != lead.suit()
+                    && !(hand & CardSet::of_suit(lead.suit())).is_empty() =>
+            {
+                Err(PlayError::MustFollowSuit)
+            }
+            _ => Ok(()),
+        }
+    }
 
     /// Contains the logic to determine who won the trick in a standard
     /// tressette game: The winner of the trick is always the player who played
@@ -25,7 +158,8 @@ impl TrickTakingGame for TressetteRules {
     /// to only be used internally by `OngoingTrick`, however it's possible to
     /// call it elsewhere if needed. It also assumes the slice `cards` is valid
     /// for the tressette game, so it assumes there are no duplicates. It's a
-    /// responsability of the caller to make sure that's the case.
+    /// responsability of the caller to make sure that's the case. Tressette
+    /// has no trump, so the context of the hand is ignored.
     ///
     /// # Panics
     ///
@@ -34,7 +168,7 @@ impl TrickTakingGame for TressetteRules {
     /// # Examples
     ///
     /// ```
-    /// use shuftlib::common::{hands::{TrickTakingGame, PlayerId}, cards::{ItalianRank, Suit}};
+    /// use shuftlib::common::{hands::{HandContext, TrickTakingGame, PlayerId}, cards::{ItalianRank, Suit}};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
     /// let cards = [
@@ -44,14 +178,17 @@ impl TrickTakingGame for TressetteRules {
     ///   TressetteCard::new(ItalianRank::Four, Suit::Hearts),
     /// ];
     ///
-    /// let taker = TressetteRules::determine_taker(&cards, PlayerId::new(2).unwrap());
-    /// assert_eq!(taker, PlayerId::new(2).unwrap());
+    /// let rules = TressetteRules::default();
+    /// let taker = rules.determine_taker(&cards, rules.player(2).unwrap(), &HandContext::default());
+    /// assert_eq!(taker, rules.player(2).unwrap());
     /// ```
     #[allow(clippy::expect_used)]
     fn determine_taker(
-        cards: &[TressetteCard; Self::PLAYERS],
-        first_to_play: PlayerId<{ Self::PLAYERS }>,
-    ) -> PlayerId<{ Self::PLAYERS }> {
+        &self,
+        cards: &[TressetteCard],
+        first_to_play: PlayerId,
+        _context: &HandContext,
+    ) -> PlayerId {
         let leading_suit = cards[*first_to_play].suit();
         let (taker, _) = cards
             .iter()
@@ -60,19 +197,180 @@ impl TrickTakingGame for TressetteRules {
             .max_by_key(|(_, &c)| c)
             .expect("Max by key returned None. This shouldn't have happened, since it's being called on a non empty slice.");
 
-        PlayerId::new(taker).expect("Initialization of a new PlayerId failed. This shouldn't have happened, since the input usize was computed starting from a fixed length slice.")
+        PlayerId::new(taker, first_to_play.players()).expect("Initialization of a new PlayerId failed. This shouldn't have happened, since the input usize was computed starting from a fixed length slice.")
+    }
+}
+
+impl MatchRules for TressetteRules {
+    type Signal = Signal;
+
+    fn deck(&self) -> Deck<TressetteCard> {
+        Deck::from_vec(Deck::italian().iter().map(|&c| c.into()).collect())
+    }
+
+    /// Every player receives 10 cards, in two packets of 5. In two, each
+    /// player draws a card in the open after every trick.
+    fn dealing(&self, dealer: PlayerId) -> Dealing {
+        let dealing = Dealing::new(dealer, vec![5, 5]);
+        match self.players {
+            2 => dealing.with_draws(1).with_open_draws(),
+            _ => dealing,
+        }
+    }
+
+    /// Players 0 and 2 play against players 1 and 3. In two, everyone plays
+    /// on their own.
+    fn teams(&self) -> Teams {
+        Teams::partnerships(self.players, 2).unwrap_or_else(|| Teams::individual(self.players))
+    }
+
+    /// If declarations are enabled, every `Declaration` allowed by `hand`
+    /// that hasn't been declared yet.
+    fn legal_declarations(
+        &self,
+        hand: CardSet<TressetteCard>,
+        declared: &[CardSet<TressetteCard>],
+    ) -> Vec<CardSet<TressetteCard>> {
+        if !self.options.declarations {
+            return Vec::new();
+        }
+
+        Self::declarable(hand)
+            .into_iter()
+            .map(|d| d.cards())
+            .filter(|cards| !declared.contains(cards))
+            .collect()
+    }
+
+    /// The points of the `Declaration` made with `cards`.
+    fn declaration_points(&self, cards: CardSet<TressetteCard>) -> u32 {
+        Declaration::from_cards(cards).map_or(0, |d| d.points())
+    }
+
+    /// If signals are enabled in the four players game, the player leading a
+    /// trick can knock, asking their partner to take it and lead the suit
+    /// back. They can also tell whether they hold more cards of the suit led,
+    /// by sliding the card, or not, by throwing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::{card_set::CardSet, cards::{ItalianRank, Suit}, game::MatchRules, hands::{OngoingTrick, TrickTakingGame}};
+    /// use shuftlib::tressette::{Signal, TressetteCard, TressetteOptions, TressetteRules};
+    ///
+    /// let options = TressetteOptions::default().with_signals(true);
+    /// let rules = TressetteRules::default().with_options(options);
+    /// let trick = OngoingTrick::new(&rules, rules.player(0).unwrap()).unwrap();
+    /// let three = TressetteCard::new(ItalianRank::Three, Suit::Clubs);
+    /// let hand: CardSet<_> = [three].into_iter().collect();
+    ///
+    /// assert_eq!(rules.legal_signals(hand, three, &trick), vec![Signal::Busso, Signal::Volo]);
+    /// assert!(TressetteRules::default().legal_signals(hand, three, &trick).is_empty());
+    /// ```
+    fn legal_signals(
+        &self,
+        hand: CardSet<TressetteCard>,
+        card: TressetteCard,
+        trick: &OngoingTrick<Self>,
+    ) -> Vec<Signal> {
+        if !self.options.signals || self.players != Self::PLAYERS || trick.lead().is_some() {
+            return Vec::new();
+        }
+
+        let suit = hand & CardSet::of_suit(card.suit());
+        if suit.len() > 1 {
+            vec![Signal::Busso, Signal::Striscio]
+        } else {
+            vec![Signal::Busso, Signal::Volo]
+        }
+    }
+}
+
+impl Scoring for TressetteRules {
+    /// The score is kept for the team of players 0 and 2 and for the team of
+    /// players 1 and 3, or for each player in two.
+    fn score_hand(&self, hand: &Hand<Self>, teams: &Teams) -> HandScore {
+        Self::compute_score(hand, teams)
+    }
+
+    /// By default a team wins once it reaches 31 points and it's ahead of the
+    /// other one.
+    fn target(&self) -> Target {
+        self.target
     }
 }
 
 /// The score a team has to reach to win a game of tressette.
-pub const SCORE_TO_WIN: u8 = 31;
+pub const SCORE_TO_WIN: u32 = 31;
 
 impl TressetteRules {
-    /// Determines if a team won the game. A team wins the game when its score is
-    /// greater than 31 and has a higher score than the other team.
-    pub fn is_completed(score: (u8, u8)) -> bool {
-        (score.0 >= SCORE_TO_WIN && score.0 > score.1)
-            || (score.1 >= SCORE_TO_WIN && score.1 > score.0)
+    /// The number of players of the four players game.
+    pub const PLAYERS: usize = 4;
+    /// The number of tricks of a hand of the four players game.
+    pub const TRICKS: usize = 10;
+
+    /// Creates the rules for a game of `players` players, or None if
+    /// tressette can't be played by that many: it's played either in two or
+    /// in four.
+    pub fn new(players: usize) -> Option<Self> {
+        [2, 4].contains(&players).then(|| Self {
+            players,
+            ..Self::default()
+        })
+    }
+
+    /// Sets the score to reach to win the match, such as 21 points for a
+    /// shorter one.
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets the variants of the game played.
+    pub fn with_options(mut self, options: TressetteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the variants of the game played.
+    pub fn options(&self) -> TressetteOptions {
+        self.options
+    }
+
+    /// Returns every combination that can be declared with the cards of
+    /// `hand`. Holding all four Aces, Twos or Threes only allows to declare
+    /// the four of them, not three.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::tressette::{Declaration, TressetteRules, TressetteCard};
+    /// use shuftlib::common::{card_set::CardSet, cards::{Suit, ItalianRank}};
+    ///
+    /// let hand: CardSet<TressetteCard> = [
+    ///     TressetteCard::new(ItalianRank::Ace, Suit::Hearts),
+    ///     TressetteCard::new(ItalianRank::Two, Suit::Hearts),
+    ///     TressetteCard::new(ItalianRank::Three, Suit::Hearts),
+    ///     TressetteCard::new(ItalianRank::Ace, Suit::Clubs),
+    ///     TressetteCard::new(ItalianRank::Ace, Suit::Spades),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let declarable = TressetteRules::declarable(hand);
+    /// assert_eq!(declarable, vec![
+    ///     Declaration::Napoletana(Suit::Hearts),
+    ///     Declaration::Three { rank: ItalianRank::Ace, missing: Suit::Diamonds },
+    /// ]);
+    /// ```
+    pub fn declarable(hand: CardSet<TressetteCard>) -> Vec<Declaration> {
+        Declaration::every()
+            .filter(|d| d.cards().is_subset(hand))
+            .filter(|d| match *d {
+                Declaration::Three { rank, .. } => !Declaration::Four(rank).cards().is_subset(hand),
+                _ => true,
+            })
+            .collect()
     }
 
     /// Returns a view of the playable cards held by a player, based on the suit
@@ -83,10 +381,10 @@ impl TressetteRules {
     ///
     /// ```
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
-    /// use shuftlib::common::hands::Player;
+    /// use shuftlib::common::hands::{Player, PlayerId};
     /// use shuftlib::common::cards::{Suit, ItalianRank};
     ///
-    /// let mut player = Player::default();
+    /// let mut player = Player::new(PlayerId::new(0, 4).unwrap());
     /// player.give(TressetteCard::new(ItalianRank::Ace, Suit::Spades));
     /// player.give(TressetteCard::new(ItalianRank::Two, Suit::Spades));
     /// player.give(TressetteCard::new(ItalianRank::Ace, Suit::Hearts));
@@ -97,59 +395,140 @@ impl TressetteRules {
     pub fn playable(
         player: &Player<TressetteRules>,
         leading_suit: Option<Suit>,
-    ) -> Vec<TressetteCard> {
+    ) -> CardSet<TressetteCard> {
         if let Some(leading_suit) = leading_suit {
-            if player.hand().iter().any(|c| c.suit() == leading_suit) {
-                return player
-                    .hand()
-                    .iter()
-                    .filter(|c| c.suit() == leading_suit)
-                    .cloned()
-                    .collect();
+            let same_suit = player.hand() & CardSet::of_suit(leading_suit);
+            if !same_suit.is_empty() {
+                return same_suit;
             }
         }
 
-        player.hand().into()
-    }
-
-    /// Plays the specified card for the player
-    pub fn play(
-        player: &mut Player<TressetteRules>,
-        card: TressetteCard,
-        ongoing_trick: &mut OngoingTrick<TressetteRules>,
-    ) {
-        player.remove(card);
-        ongoing_trick.play(card);
+        player.hand()
     }
 
-    /// Computes the score for a hand of the tressette game.
-    /// Score is always a maximum of 11 points.
-    pub fn compute_score(hand: &Hand<Self>, score: &mut (u8, u8)) {
-        let mut tmp_score = (Rational32::new(0, 3), Rational32::new(0, 3));
+    /// Computes the score of every team for a hand of the tressette game.
+    /// Only whole points are counted, so the score of a hand is always a
+    /// maximum of 11 points: 10 for the cards and 1 for the last trick. The
+    /// points of the declarations are added by the match, see
+    /// `MatchRules::declaration_points`.
+    pub fn compute_score(hand: &Hand<Self>, teams: &Teams) -> HandScore {
+        let mut cards = vec![Rational32::new(0, 3); teams.count()];
 
-        let mut taker = 0;
         for trick in hand.tricks() {
-            if *trick.taker() == 0 || *trick.taker() == 2 {
-                tmp_score.0 += trick.cards().iter().map(|c| c.value()).sum::<Rational32>();
-            } else {
-                tmp_score.1 += trick.cards().iter().map(|c| c.value()).sum::<Rational32>();
+            if let Some(points) = teams
+                .team_of(trick.taker())
+                .and_then(|team| cards.get_mut(*team))
+            {
+                *points += trick.cards().iter().map(|c| c.value()).sum::<Rational32>();
             }
-
-            taker = *trick.taker();
         }
 
-        score.0 += tmp_score.0.to_integer() as u8;
-        score.1 += tmp_score.1.to_integer() as u8;
+        let last_trick = hand
+            .tricks()
+            .last()
+            .and_then(|trick| teams.team_of(trick.taker()));
+
+        HandScore::new(
+            teams
+                .teams()
+                .zip(cards)
+                .map(|(team, points)| {
+                    ScoreBreakdown::default()
+                        .with_cards(points.to_integer() as u32)
+                        .with_last_trick(u32::from(last_trick == Some(team)))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The ranks whose combinations can be declared.
+const DECLARABLE: [ItalianRank; 3] = [ItalianRank::Ace, ItalianRank::Two, ItalianRank::Three];
+
+/// A combination of cards, also known as "accuso", that a player can declare
+/// during the first trick of a hand to score some extra points, if the
+/// variant is enabled by `TressetteOptions::with_declarations`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Declaration {
+    /// The Ace, the Two and the Three of a suit, worth 3 points.
+    Napoletana(Suit),
+    /// Three Aces, Twos or Threes, worth 3 points.
+    Three {
+        /// The rank of the cards.
+        rank: ItalianRank,
+        /// The suit of the only card of that rank not declared.
+        missing: Suit,
+    },
+    /// All four Aces, Twos or Threes, worth 4 points.
+    Four(ItalianRank),
+}
 
-        if taker == 0 || taker == 2 {
-            score.0 += 1;
-        } else {
-            score.1 += 1;
+impl Declaration {
+    /// Returns the points scored with this declaration.
+    pub fn points(&self) -> u32 {
+        match self {
+            Declaration::Napoletana(_) | Declaration::Three { .. } => 3,
+            Declaration::Four(_) => 4,
+        }
+    }
+
+    /// Returns the cards making this declaration.
+    pub fn cards(&self) -> CardSet<TressetteCard> {
+        match *self {
+            Declaration::Napoletana(suit) => DECLARABLE
+                .iter()
+                .map(|&rank| TressetteCard::new(rank, suit))
+                .collect(),
+            Declaration::Three { rank, missing } => Suit::iter()
+                .filter(|&suit| suit != missing)
+                .map(|suit| TressetteCard::new(rank, suit))
+                .collect(),
+            Declaration::Four(rank) => Suit::iter()
+                .map(|suit| TressetteCard::new(rank, suit))
+                .collect(),
         }
     }
+
+    /// Returns the declaration made by exactly these cards, if any.
+    pub fn from_cards(cards: CardSet<TressetteCard>) -> Option<Self> {
+        Self::every().find(|d| d.cards() == cards)
+    }
+
+    /// Returns every declaration, Napoletane first.
+    fn every() -> impl Iterator<Item = Self> {
+        let napoletane = Suit::iter().map(Declaration::Napoletana);
+        let sets = DECLARABLE.into_iter().flat_map(|rank| {
+            Suit::iter()
+                .map(move |missing| Declaration::Three { rank, missing })
+                .chain(std::iter::once(Declaration::Four(rank)))
+        });
+        napoletane.chain(sets)
+    }
+}
+
+/// The signals a player can make to their partner about the card they are
+/// playing, when enabled by `TressetteOptions::with_signals`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Signal {
+    /// The player knocks on the table: the partner should take the trick, if
+    /// they can, and lead the same suit back.
+    Busso,
+    /// The player slides the card on the table: they hold more cards of the
+    /// same suit.
+    Striscio,
+    /// The player throws the card in the air: it's their last one of the
+    /// suit.
+    Volo,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 /// Representation of a card used in variations of the Tressette game. It's just
 /// a new type over `ItalianCard`.
 pub struct TressetteCard {
@@ -191,8 +570,48 @@ impl Display for TressetteCard {
     }
 }
 
+impl FromStr for TressetteCard {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<ItalianCard>().map(TressetteCard::from)
+    }
+}
+
 impl Card for TressetteCard {}
 
+impl IndexedCard for TressetteCard {
+    const COUNT: u8 = ItalianCard::COUNT;
+
+    fn index(&self) -> u8 {
+        self.card.index()
+    }
+
+    fn from_index(index: u8) -> Option<Self> {
+        ItalianCard::from_index(index).map(TressetteCard::from)
+    }
+}
+
+impl Suited for TressetteCard {
+    fn suit(&self) -> Suit {
+        self.card.suit()
+    }
+}
+
+impl Ranked for TressetteCard {
+    type Rank = ItalianRank;
+
+    fn rank(&self) -> ItalianRank {
+        self.card.rank()
+    }
+}
+
+impl Glyph for TressetteCard {
+    fn fmt_glyph(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.card.fmt_glyph(f)
+    }
+}
+
 impl From<ItalianCard> for TressetteCard {
     fn from(value: ItalianCard) -> Self {
         TressetteCard { card: value }
@@ -259,18 +678,38 @@ impl TressetteCard {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use crate::{
         common::{
-            cards::{ItalianRank, Suit},
+            card_set::CardSet,
+            cards::{ItalianRank, ShuffleSeed, Suit},
+            events::Event,
+            game::{Action, DeclarationError, Match, MatchError},
             hands::{Player, PlayerId, TrickTakingGame},
+            scoring::{HandScore, MatchScore, ScoreBreakdown, Scoring},
         },
         tressette::SCORE_TO_WIN,
     };
     use prop::collection::hash_set;
     use proptest::prelude::*;
-
-    use super::{TressetteCard, TressetteRules};
+    use strum::IntoEnumIterator;
+
+    use super::{Declaration, Signal, TressetteCard, TressetteOptions, TressetteRules};
+
+    /// Returns true if a match of tressette with the given score is over.
+    fn is_completed(score: (u32, u32)) -> bool {
+        let mut match_score = MatchScore::default();
+        match_score.add(HandScore::new(vec![
+            ScoreBreakdown::default().with_cards(score.0),
+            ScoreBreakdown::default().with_cards(score.1),
+        ]));
+
+        TressetteRules::default()
+            .target()
+            .outcome(&match_score)
+            .is_some()
+    }
 
     fn tressette_card_strategy() -> impl Strategy<Value = TressetteCard> {
         (
@@ -302,7 +741,8 @@ mod tests {
             hash_set(tressette_card_strategy(), 1..TressetteRules::TRICKS),
         )
             .prop_map(|(index, cards)| {
-                let mut player = Player::new(PlayerId::new(index).unwrap());
+                let mut player =
+                    Player::new(PlayerId::new(index, TressetteRules::PLAYERS).unwrap());
                 for card in cards {
                     player.give(card);
                 }
@@ -310,34 +750,227 @@ mod tests {
             })
     }
 
+    #[test]
+    fn four_of_a_kind_is_declared_once() {
+        let hand: CardSet<TressetteCard> = Suit::iter()
+            .map(|suit| TressetteCard::new(ItalianRank::Three, suit))
+            .collect();
+        assert_eq!(
+            TressetteRules::declarable(hand),
+            vec![Declaration::Four(ItalianRank::Three)]
+        );
+        assert_eq!(
+            Declaration::from_cards(hand),
+            Some(Declaration::Four(ItalianRank::Three))
+        );
+        assert_eq!(Declaration::from_cards(CardSet::new()), None);
+    }
+
+    #[test]
+    fn declarations_are_only_made_in_the_first_trick() {
+        let options = TressetteOptions::default().with_declarations(true);
+        let rules = TressetteRules::default().with_options(options);
+        let mut tressette = Match::new(rules, ShuffleSeed::from(3)).unwrap();
+        let player = tressette.next_to_play().unwrap();
+        let hand = tressette.players()[*player].hand();
+        // Ten cards can't all belong to a four of a kind.
+        let cards = hand
+            .iter()
+            .map(|card| Declaration::Four(card.rank()).cards())
+            .find(|cards| !cards.is_subset(hand))
+            .unwrap();
+        assert_eq!(
+            tressette.apply(Action::Declare { player, cards }),
+            Err(MatchError::Declaration(DeclarationError::Illegal))
+        );
+
+        for _ in 0..TressetteRules::PLAYERS {
+            let play = tressette
+                .legal_moves()
+                .into_iter()
+                .find(|m| matches!(m, Action::Play { .. }))
+                .unwrap();
+            tressette.apply(play).unwrap();
+        }
+        let player = tressette.next_to_play().unwrap();
+        let hand = tressette.players()[*player].hand();
+        let cards = Declaration::Napoletana(Suit::Hearts).cards();
+        assert_eq!(
+            tressette.apply(Action::Declare {
+                player,
+                cards: cards & hand
+            }),
+            Err(MatchError::Declaration(DeclarationError::Closed))
+        );
+    }
+
+    #[test]
+    fn muto_tables_dont_signal() {
+        let rules = TressetteRules::default();
+        let mut tressette = Match::new(rules, ShuffleSeed::from(5)).unwrap();
+        let player = tressette.next_to_play().unwrap();
+        let card = tressette.players()[*player].hand().iter().next().unwrap();
+
+        assert!(tressette.legal_signals(card).is_empty());
+        let signal = Action::Play {
+            player,
+            card,
+            signal: Some(Signal::Busso),
+        };
+        assert_eq!(tressette.apply(signal), Err(MatchError::IllegalSignal));
+    }
+
     proptest! {
         #[test]
-        fn a_team_won_with_both_below(team1_score in 0u8..SCORE_TO_WIN, team2_score in 0u8..SCORE_TO_WIN) {
-            let result = TressetteRules::is_completed((team1_score, team2_score));
+        fn signals_are_made_by_the_leader(seed: u64, choices in prop::collection::vec(any::<(prop::sample::Index, prop::sample::Index)>(), 40)) {
+            let options = TressetteOptions::default().with_signals(true);
+            let rules = TressetteRules::default().with_options(options);
+            let seed = ShuffleSeed::from(seed);
+            let mut tressette = Match::new(rules.clone(), seed).unwrap();
+            for (choice, signal) in choices {
+                let player = tressette.next_to_play().unwrap();
+                let hand = tressette.players()[*player].hand();
+                let leading = tressette.current_trick().unwrap().lead().is_none();
+                let legal_moves = tressette.legal_moves();
+                let signaled = legal_moves.iter().any(|m| matches!(m, Action::Play { signal: Some(_), .. }));
+                prop_assert!(!signaled);
+                let action = match *choice.get(&legal_moves) {
+                    Action::Play { player, card, .. } => {
+                        let signals = tressette.legal_signals(card);
+                        prop_assert!(leading || signals.is_empty());
+                        let more = (hand & CardSet::of_suit(card.suit())).len() > 1;
+                        let wrong = if more { Signal::Volo } else { Signal::Striscio };
+                        prop_assert!(!signals.contains(&wrong));
+                        let signal = (!signals.is_empty()).then(|| *signal.get(&signals));
+                        Action::Play { player, card, signal }
+                    }
+                    action => action,
+                };
+                tressette.apply(action).unwrap();
+            }
+
+            let log = tressette.events();
+            let view = tressette.view(rules.player(0).unwrap()).unwrap();
+            let dealt = log.iter().rposition(|e| matches!(e, Event::Dealt { .. })).unwrap();
+            let signaled = log[dealt..].iter().filter(|e| matches!(e, Event::CardPlayed { signal: Some(_), .. })).count();
+            prop_assert_eq!(view.signals().len(), signaled);
+
+            let replayed = Match::replay(rules, seed, log, log.len()).unwrap();
+            prop_assert_eq!(replayed.events(), log);
+        }
+
+        #[test]
+        fn declarable_combinations_are_held(player in player_strategy()) {
+            let declarable = TressetteRules::declarable(player.hand());
+            for declaration in &declarable {
+                prop_assert!(declaration.cards().is_subset(player.hand()));
+                prop_assert_eq!(Declaration::from_cards(declaration.cards()), Some(*declaration));
+                if let Declaration::Three { rank, .. } = *declaration {
+                    prop_assert!(!declarable.contains(&Declaration::Four(rank)));
+                }
+            }
+        }
+
+        #[test]
+        fn declarations_are_scored(seed: u64, choices in prop::collection::vec(any::<prop::sample::Index>(), 60)) {
+            let options = TressetteOptions::default().with_declarations(true);
+            let rules = TressetteRules::default().with_options(options);
+            let mut tressette = Match::new(rules, ShuffleSeed::from(seed)).unwrap();
+            let mut choices = choices.into_iter();
+            while tressette.hands().is_empty() {
+                let legal_moves = tressette.legal_moves();
+                let action = match choices.next() {
+                    Some(choice) => *choice.get(&legal_moves),
+                    None => legal_moves[0],
+                };
+                tressette.apply(action).unwrap();
+            }
+
+            let declared: u32 = tressette
+                .events()
+                .iter()
+                .take_while(|e| !matches!(e, Event::HandScored { .. }))
+                .filter_map(|e| match *e {
+                    Event::Declared { cards, .. } => Declaration::from_cards(cards),
+                    _ => None,
+                })
+                .map(|d| d.points())
+                .sum();
+            let score = &tressette.score().hands()[0];
+            prop_assert_eq!(score.teams().iter().map(|t| t.declarations()).sum::<u32>(), declared);
+            prop_assert_eq!(score.teams().iter().map(|t| t.total()).sum::<i64>(), 11 + i64::from(declared));
+        }
+
+        #[test]
+        fn two_players_draw_in_the_open(seed: u64, moves in 1..200usize) {
+            let rules = TressetteRules::new(2).unwrap();
+            let mut tressette = Match::new(rules.clone(), ShuffleSeed::from(seed)).unwrap();
+            for _ in 0..moves {
+                if !tressette.hands().is_empty() {
+                    break;
+                }
+                let action = tressette.legal_moves()[0];
+                tressette.apply(action).unwrap();
+            }
+
+            let view = tressette.view(rules.player(0).unwrap()).unwrap();
+            let opponent = &tressette.players()[1];
+            prop_assert_eq!(view.revealed()[0], view.hand());
+            prop_assert!(view.revealed()[1].is_subset(opponent.hand()));
+            // Both players hold 10 cards until the stock is over.
+            if !tressette.stock().is_empty() {
+                prop_assert!(view.cards_left().iter().all(|&c| c == 10 || c == 9));
+            } else {
+                prop_assert_eq!(view.revealed()[1], opponent.hand());
+            }
+
+            let dealt = tressette
+                .events()
+                .iter()
+                .rposition(|e| matches!(e, Event::Dealt { .. }))
+                .unwrap();
+            let drawn: Vec<_> = tressette.events()[dealt..].iter().filter_map(|e| match e {
+                Event::Drawn { player, card } if **player == 1 => Some(*card),
+                _ => None,
+            }).collect();
+            for card in drawn {
+                prop_assert_eq!(view.revealed()[1].contains(card), opponent.hand().contains(card));
+            }
+
+            if let Some(hand) = tressette.hands().first() {
+                prop_assert_eq!(hand.tricks().len(), 20);
+                let points: i64 = tressette.score().hands()[0].teams().iter().map(|t| t.total()).sum();
+                prop_assert_eq!(points, 11);
+            }
+        }
+
+        #[test]
+        fn a_team_won_with_both_below(team1_score in 0u32..SCORE_TO_WIN, team2_score in 0u32..SCORE_TO_WIN) {
+            let result = is_completed((team1_score, team2_score));
             assert!(!result);
         }
 
         #[test]
-        fn a_team_won_with_both_above_and_same(score in SCORE_TO_WIN..u8::MAX) {
-            let result = TressetteRules::is_completed((score, score));
+        fn a_team_won_with_both_above_and_same(score in SCORE_TO_WIN..u32::MAX) {
+            let result = is_completed((score, score));
             assert!(!result);
         }
 
         #[test]
-        fn a_team_won_with_both_above_and_different(score in SCORE_TO_WIN..u8::MAX) {
-            let result = TressetteRules::is_completed((score, score+1));
+        fn a_team_won_with_both_above_and_different(score in SCORE_TO_WIN..u32::MAX) {
+            let result = is_completed((score, score+1));
             assert!(result);
         }
 
         #[test]
-        fn a_team_won_with_team1_above(team1_score in 0u8..SCORE_TO_WIN, team2_score in SCORE_TO_WIN..u8::MAX) {
-            let result = TressetteRules::is_completed((team1_score, team2_score));
+        fn a_team_won_with_team1_above(team1_score in 0u32..SCORE_TO_WIN, team2_score in SCORE_TO_WIN..u32::MAX) {
+            let result = is_completed((team1_score, team2_score));
             assert!(result);
         }
 
         #[test]
-        fn a_team_won_with_team2_above(team1_score in SCORE_TO_WIN..u8::MAX, team2_score in 0u8..SCORE_TO_WIN ) {
-            let result = TressetteRules::is_completed((team1_score, team2_score));
+        fn a_team_won_with_team2_above(team1_score in SCORE_TO_WIN..u32::MAX, team2_score in 0u32..SCORE_TO_WIN ) {
+            let result = is_completed((team1_score, team2_score));
             assert!(result);
         }
 
@@ -0,0 +1,65 @@
+//! Plays whole games of briscola through the public API of the crate.
+
+use shuftlib::{
+    briscola::BriscolaRules,
+    common::{
+        cards::{ShuffleSeed, Suited},
+        events::Event,
+        game::Match,
+        hands::TrickTakingGame,
+        scoring::Outcome,
+    },
+};
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn briscola_works() {
+    for players in 2..=4 {
+        let rules = BriscolaRules::new(players).unwrap();
+        let mut briscola = Match::new(rules.clone(), ShuffleSeed::random()).unwrap();
+        let trump = briscola.face_up()[0];
+
+        while !briscola.is_over() {
+            // Nobody can hold more than 3 cards.
+            assert!(briscola.players().iter().all(|p| p.hand().len() <= 3));
+            assert_eq!(briscola.legal_moves().len(), {
+                let player = briscola.next_to_play().unwrap();
+                briscola.players()[*player].hand().len()
+            });
+
+            let action = briscola.legal_moves()[0];
+            briscola.apply(action).unwrap();
+        }
+
+        // After every trick the taker draws first, and the trump is the last
+        // card drawn.
+        let events = briscola.events();
+        for (i, event) in events.iter().enumerate() {
+            if let (Event::TrickTaken { trick }, Some(Event::Drawn { player, .. })) =
+                (event, events.get(i + 1))
+            {
+                assert_eq!(*player, trick.taker());
+            }
+        }
+        let last_drawn = events.iter().rev().find_map(|e| match e {
+            Event::Drawn { card, .. } => Some(*card),
+            _ => None,
+        });
+        assert_eq!(last_drawn, Some(trump));
+
+        let hand = &briscola.hands()[0];
+        assert_eq!(hand.tricks().len(), rules.tricks());
+        assert_eq!(hand.context().trump(), Some(trump.suit()));
+
+        let score = briscola.score();
+        assert_eq!(
+            score.totals().iter().sum::<i64>(),
+            i64::from(BriscolaRules::POINTS)
+        );
+        assert_eq!(score.totals().len(), if players == 4 { 2 } else { players });
+        match briscola.outcome().unwrap() {
+            Outcome::Won(winner) => assert_eq!(score.leaders(), vec![winner]),
+            Outcome::Draw => assert!(score.leaders().len() > 1),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Deals hands of tressette between peers through the mental poker protocol,
+//! without anyone knowing the whole deck.
+
+use num_bigint::BigUint;
+use shuftlib::{
+    common::{
+        card_set::CardSet,
+        cards::Deck,
+        game::MatchRules,
+        hands::{Player, TrickTakingGame},
+        mental_poker::{CardKey, Group, MaskedDeck, Participant},
+    },
+    tressette::{TressetteCard, TressetteRules},
+};
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn tressette_is_dealt_between_peers() {
+    let mut rng = rand::thread_rng();
+    // A small group keeps the test fast, real games use `Group::rfc3526`.
+    let group = Group::new(BigUint::from(9223372036854778487u64)).unwrap();
+
+    for players in [2, 4] {
+        let rules = TressetteRules::new(players).unwrap();
+        let dealer = rules.player(players - 1).unwrap();
+        let dealing = rules.dealing(dealer);
+        let mut peers: Vec<_> = (0..players)
+            .map(|_| Participant::new(group.clone()))
+            .collect();
+
+        // Every peer shuffles and then locks the deck in turn, receiving it
+        // from the previous one as bytes.
+        let mut deck = MaskedDeck::new(group.clone(), &rules.deck()).unwrap();
+        for peer in &mut peers {
+            deck = MaskedDeck::from_bytes(group.clone(), &rules.deck(), &deck.to_bytes()).unwrap();
+            peer.shuffle(&mut deck, &mut rng);
+        }
+        for peer in &mut peers {
+            deck = MaskedDeck::from_bytes(group.clone(), &rules.deck(), &deck.to_bytes()).unwrap();
+            peer.lock(&mut deck, &mut rng).unwrap();
+        }
+
+        // Each player opens their own cards with the keys of everyone else.
+        let hands: Vec<CardSet<TressetteCard>> = (0..players)
+            .map(|i| {
+                let id = rules.player(i).unwrap();
+                deck.dealt_to(&dealing, id)
+                    .into_iter()
+                    .map(|position| {
+                        let keys: Vec<CardKey> = peers
+                            .iter()
+                            .map(|p| p.card_key(position).unwrap())
+                            .collect();
+                        deck.open(position, &keys).unwrap()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        hands
+            .iter()
+            .for_each(|h| assert_eq!(h.len(), dealing.cards_per_player()));
+        let dealt: CardSet<TressetteCard> = hands.iter().flat_map(|h| h.iter()).collect();
+        assert_eq!(dealt.len(), dealing.cards_needed());
+
+        // Once the hand is over, anyone can check the deal and see that the
+        // opened deck gives everyone the cards they've been playing with.
+        let reveals: Vec<_> = peers.iter().map(Participant::reveal).collect();
+        let mut opened = Deck::from_vec(deck.verify(&reveals).unwrap());
+        let mut seats: Vec<Player<TressetteRules>> = (0..players)
+            .map(|i| Player::new(rules.player(i).unwrap()))
+            .collect();
+        opened.deal(&mut seats, &dealing).unwrap();
+
+        seats
+            .iter()
+            .zip(&hands)
+            .for_each(|(seat, hand)| assert_eq!(seat.hand(), *hand));
+        assert_eq!(opened.len(), rules.deck().len() - dealing.cards_needed());
+    }
+}
@@ -1,58 +1,114 @@
-#![feature(generic_const_exprs)]
+//! Plays whole games of tressette through the public API of the crate.
+
 use shuftlib::{
     common::{
-        cards::Deck,
-        hands::{OngoingHand, OngoingTrick, Player, PlayerId, TrickTakingGame},
+        cards::{ShuffleSeed, Suited},
+        game::{Action, Match},
+        hands::TrickTakingGame,
+        scoring::{Outcome, Target},
     },
-    tressette::{self, TressetteCard, TressetteRules},
+    tressette::{self, TressetteRules},
 };
 
 #[test]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::panic)]
 fn tressette_works() {
-    let mut first = true;
-    let mut leading_suit = None;
-    let first_to_play = PlayerId::new(0).unwrap();
-    let mut score = (0, 0);
-    let mut players = [
-        Player::new(PlayerId::new(0).unwrap()),
-        Player::new(PlayerId::new(1).unwrap()),
-        Player::new(PlayerId::new(2).unwrap()),
-        Player::new(PlayerId::new(3).unwrap()),
-    ];
-    let mut hands = Vec::new();
-
-    while !TressetteRules::is_completed(score) {
-        let mut ongoing_hand = OngoingHand::<TressetteRules>::new();
-        let mut deck = Deck::italian();
-        deck.shuffle();
-
-        for (i, &card) in deck.iter().enumerate() {
-            let player_index = (i / 5) % TressetteRules::PLAYERS;
-            players[player_index].give(TressetteCard::from(card));
-        }
+    let mut tressette = Match::new(TressetteRules::default(), ShuffleSeed::random()).unwrap();
+
+    while !tressette.is_over() {
+        let trick = tressette.current_trick().unwrap();
+        let player = &tressette.players()[*tressette.next_to_play().unwrap()];
+        let leading_suit = trick.lead().map(|c| c.suit());
+        let playable = TressetteRules::playable(player, leading_suit);
+
+        let legal_moves = tressette.legal_moves();
+        assert_eq!(legal_moves.len(), playable.len());
+
+        let action = legal_moves[0];
+        tressette.apply(action).unwrap();
+    }
+
+    let score = tressette.score();
+    assert_eq!(score.hands().len(), tressette.hands().len());
+    for hand in score.hands() {
+        let points: Vec<i64> = hand.teams().iter().map(|t| t.total()).collect();
+        assert_eq!(points.iter().sum::<i64>(), 11);
+        assert_eq!(hand.teams().iter().map(|t| t.last_trick()).sum::<u32>(), 1);
+    }
+
+    let totals = score.totals();
+    assert_ne!(totals[0], totals[1]);
+    assert!(totals
+        .iter()
+        .any(|&t| t >= i64::from(tressette::SCORE_TO_WIN)));
+    let Some(Outcome::Won(winner)) = tressette.outcome() else {
+        panic!("The match should have been won by a team.");
+    };
+    assert_eq!(score.leaders(), vec![winner]);
+    assert!(!tressette.hands().is_empty());
+
+    for hand in tressette.hands() {
+        assert_eq!(hand.tricks().len(), TressetteRules::TRICKS);
+        // Every trick after the first one is led by the taker of the previous
+        // one, who has to be followed by the other players if possible.
+        hand.tricks().windows(2).for_each(|tricks| {
+            let lead = tricks[1].cards()[*tricks[0].taker()];
+            assert_eq!(tricks[1].taken_with().suit(), lead.suit());
+        });
+    }
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn cheating_is_rejected() {
+    let rules = TressetteRules::default();
+    let mut tressette = Match::new(rules.clone(), ShuffleSeed::from(7)).unwrap();
+    let next = tressette.next_to_play().unwrap();
+    let mut other = next;
+    other.inc();
+
+    // The cards of another player can't be played, by anyone.
+    let card = tressette.players()[*other].hand().iter().next().unwrap();
+    assert!(tressette
+        .apply(Action::Play {
+            player: next,
+            card,
+            signal: None
+        })
+        .is_err());
+    assert!(tressette
+        .apply(Action::Play {
+            player: other,
+            card,
+            signal: None
+        })
+        .is_err());
+
+    assert_eq!(tressette.next_to_play(), Some(next));
+    assert_eq!(tressette.players()[*other].hand().len(), rules.tricks());
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn two_players_tressette_works() {
+    let rules = TressetteRules::new(2).unwrap().with_target(Target::new(21));
+    let mut tressette = Match::new(rules, ShuffleSeed::random()).unwrap();
 
-        for trick_id in 0..TressetteRules::TRICKS {
-            let mut ongoing_trick = OngoingTrick::<TressetteRules>::new(first_to_play);
-            for _ in 0..TressetteRules::PLAYERS {
-                let next_to_play = ongoing_trick.next_to_play();
-                let playable = TressetteRules::playable(&players[*next_to_play], leading_suit);
-                TressetteRules::play(&mut players[*next_to_play], playable[0], &mut ongoing_trick);
-
-                if first {
-                    leading_suit = Some(playable[0].suit());
-                    first = !first;
-                }
-            }
-            first = !first;
-            ongoing_hand.add(ongoing_trick.finish().unwrap(), trick_id);
+    while !tressette.is_over() {
+        let player = tressette.next_to_play().unwrap();
+        // Until the stock is over both players draw after every trick, so they
+        // always have 10 cards when a trick starts.
+        if !tressette.stock().is_empty() && tressette.current_trick().unwrap().lead().is_none() {
+            assert_eq!(tressette.players()[*player].hand().len(), 10);
         }
-        let hand = ongoing_hand.finish().unwrap();
-        TressetteRules::compute_score(&hand, &mut score);
-        hands.push(hand);
+
+        let action = tressette.legal_moves()[0];
+        tressette.apply(action).unwrap();
     }
 
-    assert_eq!((score.0 + score.1) % 11, 0);
-    assert_ne!(score.0, score.1);
-    assert!(score.0 >= tressette::SCORE_TO_WIN || score.1 >= tressette::SCORE_TO_WIN);
+    let score = tressette.score();
+    assert!(score.totals().iter().any(|&t| t >= 21));
+    for hand in tressette.hands() {
+        assert_eq!(hand.tricks().len(), 20);
+    }
 }